@@ -0,0 +1,4 @@
+// Recompila quando uma migração é adicionada, já que `sqlx::migrate!` as embute no binário.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
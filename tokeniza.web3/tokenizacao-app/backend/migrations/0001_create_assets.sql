@@ -0,0 +1,18 @@
+CREATE TABLE assets (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    asset_type TEXT NOT NULL,
+    value TEXT NOT NULL,
+    total_supply BIGINT NOT NULL,
+    available_supply BIGINT NOT NULL,
+    token_address TEXT,
+    owner TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_assets_owner ON assets (owner);
+CREATE INDEX idx_assets_status ON assets (status);
@@ -0,0 +1,13 @@
+CREATE TABLE listings (
+    id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL REFERENCES assets (id),
+    seller TEXT NOT NULL,
+    price TEXT NOT NULL,
+    quantity BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT
+);
+
+CREATE INDEX idx_listings_asset_id ON listings (asset_id);
+CREATE INDEX idx_listings_status ON listings (status);
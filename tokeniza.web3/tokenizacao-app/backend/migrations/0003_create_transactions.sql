@@ -0,0 +1,15 @@
+CREATE TABLE transactions (
+    id TEXT PRIMARY KEY NOT NULL,
+    listing_id TEXT NOT NULL REFERENCES listings (id),
+    buyer TEXT NOT NULL,
+    seller TEXT NOT NULL,
+    price TEXT NOT NULL,
+    quantity BIGINT NOT NULL,
+    tx_hash TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+
+CREATE INDEX idx_transactions_listing_id ON transactions (listing_id);
+CREATE INDEX idx_transactions_tx_hash ON transactions (tx_hash);
@@ -0,0 +1,12 @@
+CREATE TABLE waitlist_entries (
+    id TEXT PRIMARY KEY NOT NULL,
+    email TEXT NOT NULL UNIQUE,
+    name TEXT,
+    interest_areas TEXT NOT NULL,
+    investment_range TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    contacted_at TEXT
+);
+
+CREATE INDEX idx_waitlist_entries_status ON waitlist_entries (status);
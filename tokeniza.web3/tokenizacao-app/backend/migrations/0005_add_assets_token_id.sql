@@ -0,0 +1 @@
+ALTER TABLE assets ADD COLUMN token_id TEXT;
@@ -0,0 +1 @@
+ALTER TABLE listings ADD COLUMN tx_hash TEXT;
@@ -0,0 +1,19 @@
+CREATE TABLE chain_events (
+    id TEXT PRIMARY KEY NOT NULL,
+    block_number BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    contract TEXT NOT NULL,
+    event TEXT NOT NULL,
+    data TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (tx_hash, log_index)
+);
+
+CREATE INDEX idx_chain_events_block_number ON chain_events (block_number);
+
+CREATE TABLE indexer_checkpoints (
+    name TEXT PRIMARY KEY NOT NULL,
+    block_number BIGINT NOT NULL,
+    updated_at TEXT NOT NULL
+);
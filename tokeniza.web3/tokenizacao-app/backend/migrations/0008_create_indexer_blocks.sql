@@ -0,0 +1,19 @@
+CREATE TABLE indexed_blocks (
+    block_number BIGINT PRIMARY KEY NOT NULL,
+    block_hash TEXT NOT NULL,
+    parent_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE chain_effects (
+    id TEXT PRIMARY KEY NOT NULL,
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    step BIGINT NOT NULL,
+    entity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    previous TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_chain_effects_block_number ON chain_effects (block_number);
@@ -0,0 +1,27 @@
+CREATE TABLE tx_operations (
+    id TEXT PRIMARY KEY NOT NULL,
+    kind TEXT NOT NULL,
+    nonce BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_tx_operations_status ON tx_operations (status);
+CREATE INDEX idx_tx_operations_nonce ON tx_operations (nonce);
+
+CREATE TABLE tx_attempts (
+    id TEXT PRIMARY KEY NOT NULL,
+    operation_id TEXT NOT NULL REFERENCES tx_operations (id),
+    tx_hash TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    max_fee_per_gas TEXT,
+    max_priority_fee_per_gas TEXT,
+    payload TEXT NOT NULL,
+    submitted_block BIGINT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_tx_attempts_operation_id ON tx_attempts (operation_id);
+CREATE INDEX idx_tx_attempts_tx_hash ON tx_attempts (tx_hash);
@@ -0,0 +1,9 @@
+CREATE TABLE user_roles (
+    address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    granted_by TEXT,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (address, role)
+);
+
+CREATE INDEX idx_user_roles_role ON user_roles (role);
@@ -0,0 +1,11 @@
+CREATE TABLE asset_status_history (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    changed_by TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_asset_status_history_asset ON asset_status_history (asset_id, created_at);
@@ -0,0 +1,5 @@
+-- Cópia de metadata.location para filtrar em SQL sem depender das funções
+-- JSON de cada banco; linhas antigas são preenchidas na inicialização.
+ALTER TABLE assets ADD COLUMN location TEXT;
+
+CREATE INDEX idx_assets_created_at ON assets (created_at);
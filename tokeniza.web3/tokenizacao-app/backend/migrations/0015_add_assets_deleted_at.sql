@@ -0,0 +1,2 @@
+-- Exclusão lógica pela API; DELETE de fato fica restrito ao rollback de reorg.
+ALTER TABLE assets ADD COLUMN deleted_at TEXT;
@@ -0,0 +1,14 @@
+CREATE TABLE asset_documents (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    sha256 TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    uploaded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_asset_documents_asset ON asset_documents (asset_id, created_at);
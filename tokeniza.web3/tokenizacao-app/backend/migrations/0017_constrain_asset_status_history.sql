@@ -0,0 +1,24 @@
+-- 0013 criou a tabela sem NOT NULL no id e sem chave estrangeira para o
+-- ativo. Nenhum dos dois bancos altera restrições de coluna de forma
+-- portável, então a tabela é recriada; entradas de ativos que já não
+-- existem são descartadas.
+CREATE TABLE asset_status_history_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL REFERENCES assets (id),
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    changed_by TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO asset_status_history_new (id, asset_id, from_status, to_status, changed_by, reason, created_at)
+SELECT id, asset_id, from_status, to_status, changed_by, reason, created_at
+FROM asset_status_history
+WHERE id IS NOT NULL AND asset_id IN (SELECT id FROM assets);
+
+DROP TABLE asset_status_history;
+
+ALTER TABLE asset_status_history_new RENAME TO asset_status_history;
+
+CREATE INDEX idx_asset_status_history_asset ON asset_status_history (asset_id, created_at);
@@ -0,0 +1,6 @@
+-- Nome, descrição e localização em minúsculas, gravados pela aplicação: o
+-- LOWER() do SQLite só converte ASCII e buscas como "São Paulo" não
+-- encontrariam "SÃO PAULO". Linhas antigas são preenchidas na inicialização.
+ALTER TABLE assets ADD COLUMN search_name TEXT;
+ALTER TABLE assets ADD COLUMN search_description TEXT;
+ALTER TABLE assets ADD COLUMN search_location TEXT;
@@ -0,0 +1,68 @@
+-- Cada rede configurada tem os próprios ativos, operações e indexador.
+-- Linhas anteriores a esta migração ficam com chain_id 0 (NULL nos ativos)
+-- e são atribuídas à rede padrão na inicialização.
+ALTER TABLE assets ADD COLUMN chain_id BIGINT;
+
+ALTER TABLE tx_operations ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+CREATE INDEX idx_tx_operations_chain_status ON tx_operations (chain_id, status);
+
+ALTER TABLE chain_effects ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+CREATE INDEX idx_chain_effects_chain_block ON chain_effects (chain_id, block_number);
+
+-- Hash de transação, número de bloco e nome de checkpoint só são únicos
+-- dentro de uma rede; as tabelas são recriadas com a rede na chave.
+CREATE TABLE chain_events_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    chain_id BIGINT NOT NULL DEFAULT 0,
+    block_number BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    contract TEXT NOT NULL,
+    event TEXT NOT NULL,
+    data TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (chain_id, tx_hash, log_index)
+);
+
+INSERT INTO chain_events_new (id, block_number, tx_hash, log_index, contract, event, data, created_at)
+SELECT id, block_number, tx_hash, log_index, contract, event, data, created_at
+FROM chain_events;
+
+DROP TABLE chain_events;
+
+ALTER TABLE chain_events_new RENAME TO chain_events;
+
+CREATE INDEX idx_chain_events_block_number ON chain_events (chain_id, block_number);
+
+CREATE TABLE indexed_blocks_new (
+    chain_id BIGINT NOT NULL DEFAULT 0,
+    block_number BIGINT NOT NULL,
+    block_hash TEXT NOT NULL,
+    parent_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (chain_id, block_number)
+);
+
+INSERT INTO indexed_blocks_new (block_number, block_hash, parent_hash, created_at)
+SELECT block_number, block_hash, parent_hash, created_at
+FROM indexed_blocks;
+
+DROP TABLE indexed_blocks;
+
+ALTER TABLE indexed_blocks_new RENAME TO indexed_blocks;
+
+CREATE TABLE indexer_checkpoints_new (
+    chain_id BIGINT NOT NULL DEFAULT 0,
+    name TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (chain_id, name)
+);
+
+INSERT INTO indexer_checkpoints_new (name, block_number, updated_at)
+SELECT name, block_number, updated_at
+FROM indexer_checkpoints;
+
+DROP TABLE indexer_checkpoints;
+
+ALTER TABLE indexer_checkpoints_new RENAME TO indexer_checkpoints;
@@ -0,0 +1,26 @@
+-- 0016 criou a tabela sem NOT NULL no id e sem chave estrangeira para o
+-- ativo. Como em 0017, a tabela é recriada; documentos de ativos que já
+-- não existem são descartados.
+CREATE TABLE asset_documents_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL REFERENCES assets (id),
+    kind TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    sha256 TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    uploaded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO asset_documents_new (id, asset_id, kind, file_name, content_type, size, sha256, storage_key, uploaded_by, created_at)
+SELECT id, asset_id, kind, file_name, content_type, size, sha256, storage_key, uploaded_by, created_at
+FROM asset_documents
+WHERE id IS NOT NULL AND asset_id IN (SELECT id FROM assets);
+
+DROP TABLE asset_documents;
+
+ALTER TABLE asset_documents_new RENAME TO asset_documents;
+
+CREATE INDEX idx_asset_documents_asset ON asset_documents (asset_id, created_at);
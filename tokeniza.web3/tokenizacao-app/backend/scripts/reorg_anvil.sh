@@ -0,0 +1,81 @@
+#!/bin/bash
+
+# Verifica o indexador contra um reorg forçado em um Anvil local:
+# um mint é indexado, o Anvil volta a um snapshot anterior a ele e a cadeia
+# segue com outro mint no lugar. O backend precisa desfazer o primeiro ativo
+# e indexar apenas o segundo.
+#
+# Requer anvil, forge e cast (Foundry), curl e jq.
+
+set -euo pipefail
+
+BACKEND_DIR=$(cd "$(dirname "$0")/.." && pwd)
+ONCHAIN_DIR="$BACKEND_DIR/../onchain"
+
+RPC_URL=http://127.0.0.1:8546
+API_URL=http://127.0.0.1:18081/api/v1
+
+# Contas padrão do Anvil (mnemonic "test test ... junk")
+PRIVATE_KEY=0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
+OWNER_A=0x70997970c51812dc3a010c7d01b50e0d17dc79c8
+OWNER_B=0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc
+
+WORK_DIR=$(mktemp -d)
+PIDS=()
+trap 'kill "${PIDS[@]}" 2>/dev/null || true; rm -rf "$WORK_DIR"' EXIT
+
+wait_for_owner() {
+    local owner=$1
+    for _ in $(seq 1 30); do
+        if curl -sf "$API_URL/assets" | jq -e --arg owner "$owner" \
+            '[.data[] | select(.name == "AssetToken #0")] | length == 1 and .[0].owner == $owner' \
+            > /dev/null; then
+            return 0
+        fi
+        sleep 1
+    done
+    echo "❌ AssetToken #0 não foi indexado para $owner"
+    curl -s "$API_URL/assets" | jq '.data[] | {name, owner, status}'
+    exit 1
+}
+
+mint_to() {
+    cast send "$ASSET_TOKEN" "mintAsset(address,string,string,uint256,string)" \
+        "$1" "ipfs://reorg/$1" RealEstate 1000 "Local" \
+        --rpc-url "$RPC_URL" --private-key "$PRIVATE_KEY" > /dev/null
+}
+
+anvil --port 8546 --chain-id 31337 --silent &
+PIDS+=($!)
+sleep 2
+
+ASSET_TOKEN=$(cd "$ONCHAIN_DIR" && forge create src/AssetToken.sol:AssetToken \
+    --rpc-url "$RPC_URL" --private-key "$PRIVATE_KEY" --broadcast --json | jq -r .deployedTo)
+echo "📄 AssetToken em $ASSET_TOKEN"
+
+(cd "$BACKEND_DIR" && cargo build --quiet)
+PORT=18081 \
+DATABASE_URL="sqlite:$WORK_DIR/reorg.db" \
+RPC_URL="$RPC_URL" \
+CHAIN_ID=31337 \
+PRIVATE_KEY="$PRIVATE_KEY" \
+ASSET_TOKEN_ADDRESS="$ASSET_TOKEN" \
+INDEXER_START_BLOCK=0 \
+INDEXER_POLL_INTERVAL_SECS=1 \
+    "$BACKEND_DIR/target/debug/tokenizacao-backend" > "$WORK_DIR/backend.log" 2>&1 &
+PIDS+=($!)
+
+SNAPSHOT=$(cast rpc evm_snapshot --rpc-url "$RPC_URL" | tr -d '"')
+
+mint_to "$OWNER_A"
+wait_for_owner "$OWNER_A"
+echo "✅ Mint para $OWNER_A indexado"
+
+# Descarta o bloco do primeiro mint e constrói uma cadeia mais longa por cima.
+cast rpc evm_revert "$SNAPSHOT" --rpc-url "$RPC_URL" > /dev/null
+mint_to "$OWNER_B"
+cast rpc anvil_mine 3 --rpc-url "$RPC_URL" > /dev/null
+
+wait_for_owner "$OWNER_B"
+grep -q "Reorg detectado" "$WORK_DIR/backend.log"
+echo "✅ Reorg detectado, mint de $OWNER_A desfeito e $OWNER_B indexado"
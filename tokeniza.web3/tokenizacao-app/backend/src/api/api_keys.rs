@@ -0,0 +1,119 @@
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, roles, RequireRole},
+    db::ApiKeyRepository,
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{ApiKey, CreateApiKeyRequest},
+    AppState,
+};
+
+/// Prefixo fixo das chaves, para que sejam reconhecíveis em logs e varreduras
+/// de segredos.
+const KEY_PREFIX: &str = "tk_";
+
+/// Gestão de chaves de API; todas as rotas exigem `Admin`.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_api_keys).post(create_api_key))
+        .route("/:id", delete(revoke_api_key))
+}
+
+async fn list_api_keys(
+    State(state): State<AppState>,
+    _admin: RequireRole<roles::Admin>,
+) -> Result<Json<Value>, ApiError> {
+    let keys = ApiKeyRepository::new(state.db.clone()).list().await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": keys,
+        "count": keys.len()
+    })))
+}
+
+/// A chave só aparece nesta resposta; depois disso existe apenas o hash.
+async fn create_api_key(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    payload: Result<Json<CreateApiKeyRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+
+    if request.name.trim().is_empty() {
+        return Err(ApiError::Validation("Nome da chave é obrigatório".to_string()));
+    }
+    if !crate::services::blockchain::is_valid_address(&request.address) {
+        return Err(ApiError::Validation("Endereço inválido".to_string()));
+    }
+    if request.scopes.is_empty() {
+        return Err(ApiError::Validation("Informe ao menos um escopo".to_string()));
+    }
+    let rate_limit_per_minute = request
+        .rate_limit_per_minute
+        .unwrap_or(state.config.auth.api_key_rate_limit_per_minute);
+    if rate_limit_per_minute == 0 {
+        return Err(ApiError::Validation("rate_limit_per_minute deve ser maior que zero".to_string()));
+    }
+
+    let mut scopes = Vec::new();
+    for scope in request.scopes {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+
+    let secret = format!("{}{}", KEY_PREFIX, auth::random_token(32));
+    let api_key = ApiKey {
+        id: Uuid::new_v4(),
+        name: request.name.trim().to_string(),
+        prefix: secret[..KEY_PREFIX.len() + 8].to_string(),
+        address: request.address.to_lowercase(),
+        scopes,
+        rate_limit_per_minute,
+        created_by: admin.address.clone(),
+        created_at: crate::db::now(),
+        last_used_at: None,
+        revoked_at: None,
+    };
+    ApiKeyRepository::new(state.db.clone())
+        .create(&api_key, &auth::hash_token(&secret))
+        .await?;
+
+    tracing::info!(
+        "🔑 {} criou a chave de API {} ({}) com escopos {:?}",
+        admin.address,
+        api_key.prefix,
+        api_key.name,
+        api_key.scopes
+    );
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "key": secret,
+            "api_key": api_key
+        }),
+        "Chave criada; guarde-a agora, ela não será exibida novamente",
+    ))
+}
+
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    if !ApiKeyRepository::new(state.db.clone()).revoke(id).await? {
+        return Err(ApiError::NotFound);
+    }
+
+    tracing::info!("🔑 {} revogou a chave de API {}", admin.address, id);
+
+    Ok(ApiResponse::success_with_message(json!({ "id": id }), "Chave revogada"))
+}
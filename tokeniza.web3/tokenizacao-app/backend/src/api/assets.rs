@@ -1,15 +1,29 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
     response::Json,
     routing::{get, post},
     Router,
 };
 use serde_json::{json, Value};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    models::{Asset, CreateAssetRequest, TokenizeAssetRequest, AssetStatus},
+    auth::{roles, AuthUser, RequireRole},
+    db::{
+        pagination::{Cursor, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE},
+        AssetHistoryRepository, AssetRepository, DbPool,
+    },
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{
+        Asset, AssetQuery, AssetStatus, AssetStatusChange, AssetTransitionRequest,
+        CreateAssetRequest, DeleteAssetQuery, Role, TokenizeAssetRequest, UpdateAssetRequest,
+    },
+    services::{blockchain::BlockchainService, indexer::EffectWriter},
+    units::with_ether,
     AppState,
 };
 
@@ -17,131 +31,575 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", get(list_assets))
         .route("/", post(create_asset))
-        .route("/:id", get(get_asset))
+        .route("/:id", get(get_asset).patch(update_asset).delete(delete_asset))
         .route("/:id/tokenize", post(tokenize_asset))
+        .route("/:id/verify", post(verify_asset))
+        .route("/:id/submit", post(submit_asset))
+        .route("/:id/approve", post(approve_asset))
+        .route("/:id/reject", post(reject_asset))
+        .route("/:id/retire", post(retire_asset))
+        .route("/:id/history", get(asset_history))
 }
 
-async fn list_assets(State(_state): State<AppState>) -> Json<Value> {
-    // Em produção, isso viria do banco de dados
-    let assets = vec![
-        json!({
-            "id": Uuid::new_v4(),
-            "name": "Imóvel Comercial - São Paulo",
-            "description": "Prédio comercial de 5 andares na região central",
-            "asset_type": "RealEstate",
-            "value": "5000000000000000000000000", // 5M ETH em wei
-            "status": "Tokenized"
-        }),
-        json!({
-            "id": Uuid::new_v4(),
-            "name": "Arte Digital NFT",
-            "description": "Coleção exclusiva de arte digital",
-            "asset_type": "Art",
-            "value": "1000000000000000000000", // 1K ETH em wei
-            "status": "Trading"
+/// Aplica uma transição pedida pela API: recusa as que o ciclo de vida não
+/// permite e registra quem a fez no histórico. A checagem vale para o status
+/// gravado no momento da escrita, não o que o chamador leu antes.
+pub(crate) async fn change_status(
+    db: &DbPool,
+    id: Uuid,
+    to: AssetStatus,
+    changed_by: &str,
+    reason: Option<String>,
+) -> Result<Asset, ApiError> {
+    let (previous, asset) = AssetRepository::new(db.clone())
+        .modify(id, |asset| {
+            if !asset.status.can_transition_to(to) {
+                return Err(ApiError::Validation(format!(
+                    "Transição de {:?} para {:?} não permitida",
+                    asset.status, to
+                )));
+            }
+            asset.status = to;
+            Ok(())
         })
-    ];
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
-    Json(json!({
-        "success": true,
-        "data": assets,
-        "count": assets.len()
-    }))
+    AssetHistoryRepository::new(db.clone())
+        .record(&AssetStatusChange::new(id, Some(previous.status), to, changed_by, reason))
+        .await?;
+
+    tracing::info!("🔁 Ativo {}: {:?} → {:?} por {}", id, previous.status, to, changed_by);
+    Ok(asset)
+}
+
+/// Registra um status que já aconteceu on-chain (oferta criada ou
+/// cancelada), sem a checagem do ciclo de vida e no diário do indexador,
+/// para que um reorg o desfaça; não faz nada se o indexador já o aplicou.
+pub(crate) async fn apply_onchain_status(
+    writer: &mut EffectWriter,
+    id: Uuid,
+    to: AssetStatus,
+    changed_by: &str,
+    reason: Option<String>,
+) -> Result<(), ApiError> {
+    if AssetRepository::new(writer.db())
+        .find_by_id(id)
+        .await?
+        .is_none_or(|asset| asset.status == to)
+    {
+        return Ok(());
+    }
+
+    let changed = writer
+        .modify_asset_by(id, |asset| asset.status = to, changed_by, reason)
+        .await?;
+    if let Some((previous, _)) = changed.filter(|(previous, _)| previous.status != to) {
+        tracing::info!("🔁 Ativo {}: {:?} → {:?} por {}", id, previous.status, to, changed_by);
+    }
+
+    Ok(())
+}
+
+/// Busca com filtros, ordenação (`sort`, `order`) e paginação por cursor.
+async fn list_assets(
+    State(state): State<AppState>,
+    query: Result<Query<AssetQuery>, QueryRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Query(query) = query?;
+
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            Cursor::decode_for(
+                raw,
+                &crate::db::enum_to_db(&query.sort).unwrap_or_default(),
+                &crate::db::enum_to_db(&query.order).unwrap_or_default(),
+            )
+            .ok_or_else(|| ApiError::BadRequest("cursor inválido para esta ordenação".to_string()))
+        })
+        .transpose()?;
+    if let (Some(min), Some(max)) = (query.min_value, query.max_value) {
+        if min > max {
+            return Err(ApiError::Validation("min_value maior que max_value".to_string()));
+        }
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let page = AssetRepository::new(state.db.clone())
+        .search(&query, limit, cursor.as_ref())
+        .await?;
+
+    Ok(ApiResponse::paginated(
+        with_ether(json!(page.items), &["value"]),
+        &page,
+    ))
 }
 
 async fn create_asset(
-    State(_state): State<AppState>,
-    Json(request): Json<CreateAssetRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    let asset_id = Uuid::new_v4();
-    
-    // Em produção, salvar no banco de dados
-    let asset = json!({
-        "id": asset_id,
-        "name": request.name,
-        "description": request.description,
-        "asset_type": request.asset_type,
-        "value": request.value,
-        "total_supply": request.total_supply,
-        "available_supply": request.total_supply,
-        "status": "Draft",
-        "created_at": chrono::Utc::now().to_rfc3339()
-    });
+    State(state): State<AppState>,
+    user: AuthUser,
+    payload: Result<Json<CreateAssetRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    if request.name.trim().is_empty() {
+        return Err(ApiError::Validation("Nome do ativo é obrigatório".to_string()));
+    }
+    if request.total_supply == 0 || request.total_supply > i64::MAX as u64 {
+        return Err(ApiError::Validation("total_supply fora do intervalo permitido".to_string()));
+    }
 
-    Ok(Json(json!({
-        "success": true,
-        "message": "Ativo criado com sucesso",
-        "data": asset
-    })))
+    let now = crate::db::now();
+    let asset = Asset {
+        id: Uuid::new_v4(),
+        name: request.name,
+        description: request.description,
+        asset_type: request.asset_type,
+        value: request.value,
+        total_supply: request.total_supply,
+        available_supply: request.total_supply,
+        token_address: None,
+        token_id: None,
+        owner: user.address,
+        metadata: request.metadata,
+        status: AssetStatus::Draft,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+        chain_id: None,
+    };
+
+    AssetRepository::new(state.db.clone()).create(&asset).await?;
+    AssetHistoryRepository::new(state.db.clone())
+        .record(&AssetStatusChange::new(asset.id, None, asset.status, &asset.owner, None))
+        .await?;
+
+    tracing::info!("🏠 Ativo criado: {} ({})", asset.name, asset.id);
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo criado com sucesso",
+    ))
 }
 
 async fn get_asset(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, StatusCode> {
-    // Em produção, buscar no banco de dados
-    let asset = json!({
-        "id": id,
-        "name": "Imóvel Exemplo",
-        "description": "Descrição do ativo",
-        "asset_type": "RealEstate",
-        "value": "1000000000000000000000000",
-        "status": "Tokenized",
-        "token_address": "0x1234567890123456789012345678901234567890"
-    });
+) -> Result<Json<Value>, ApiError> {
+    let asset = find_asset(&state, id).await?;
 
-    Ok(Json(json!({
-        "success": true,
-        "data": asset
-    })))
+    Ok(ApiResponse::success(with_ether(json!(asset), &["value"])))
+}
+
+/// Edita nome, descrição e metadados de um ativo que ainda não foi aprovado.
+async fn update_asset(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<UpdateAssetRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let mut asset = find_asset(&state, id).await?;
+    require_owner(&asset, &user)?;
+
+    if !matches!(asset.status, AssetStatus::Draft | AssetStatus::PendingApproval) {
+        return Err(ApiError::Validation(format!(
+            "Ativos em {:?} não podem ser editados",
+            asset.status
+        )));
+    }
+    if request.name.is_none() && request.description.is_none() && request.metadata.is_none() {
+        return Err(ApiError::Validation(
+            "Informe ao menos um campo: name, description ou metadata".to_string(),
+        ));
+    }
+
+    if let Some(name) = request.name {
+        if name.trim().is_empty() {
+            return Err(ApiError::Validation("Nome do ativo é obrigatório".to_string()));
+        }
+        asset.name = name;
+    }
+    if let Some(description) = request.description {
+        asset.description = description;
+    }
+    if let Some(metadata) = request.metadata {
+        asset.metadata = metadata;
+    }
+    asset.updated_at = crate::db::next_updated_at(asset.updated_at);
+
+    if !AssetRepository::new(state.db.clone())
+        .update_if_unmodified(&asset, &request.updated_at)
+        .await?
+    {
+        return Err(modified_since(&request.updated_at));
+    }
+
+    tracing::info!("✏️ Ativo {} editado por {}", asset.id, user.address);
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo atualizado",
+    ))
+}
+
+/// Retira o ativo. Sem token ele também é excluído (logicamente) e deixa de
+/// aparecer; tokenizado, continua visível, já que o NFT segue existindo.
+async fn delete_asset(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    query: Result<Query<DeleteAssetQuery>, QueryRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Query(query) = query?;
+    let mut asset = find_asset(&state, id).await?;
+    require_owner(&asset, &user)?;
+
+    let from = asset.status;
+    let soft_delete = asset.token_id.is_none();
+    // Um ativo já retirado ainda pode ser excluído, se não tiver token.
+    if !(from.can_transition_to(AssetStatus::Retired) || (from == AssetStatus::Retired && soft_delete)) {
+        return Err(ApiError::Validation(format!(
+            "Transição de {:?} para {:?} não permitida",
+            from,
+            AssetStatus::Retired
+        )));
+    }
+
+    asset.status = AssetStatus::Retired;
+    asset.updated_at = crate::db::next_updated_at(asset.updated_at);
+    if soft_delete {
+        asset.deleted_at = Some(asset.updated_at);
+    }
+
+    if !AssetRepository::new(state.db.clone())
+        .update_if_unmodified(&asset, &query.updated_at)
+        .await?
+    {
+        return Err(modified_since(&query.updated_at));
+    }
+
+    let reason = match (soft_delete, query.reason) {
+        (true, Some(reason)) => Some(format!("excluído: {}", reason)),
+        (true, None) => Some("excluído".to_string()),
+        (false, reason) => reason,
+    };
+    AssetHistoryRepository::new(state.db.clone())
+        .record(&AssetStatusChange::new(asset.id, Some(from), asset.status, &user.address, reason))
+        .await?;
+
+    tracing::info!(
+        "🗑️ Ativo {} {} por {}",
+        asset.id,
+        if soft_delete { "excluído" } else { "retirado" },
+        user.address
+    );
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        if soft_delete { "Ativo excluído" } else { "Ativo retirado" },
+    ))
+}
+
+fn tokenizing_elsewhere() -> ApiError {
+    ApiError::Conflict("o ativo está sendo tokenizado por outra requisição".to_string())
+}
+
+fn modified_since(expected: &chrono::DateTime<chrono::Utc>) -> ApiError {
+    ApiError::Conflict(format!(
+        "o ativo foi alterado depois de {}; recarregue-o e tente novamente",
+        expected.to_rfc3339()
+    ))
 }
 
 async fn tokenize_asset(
     State(state): State<AppState>,
+    RequireRole(issuer, _): RequireRole<roles::Issuer>,
     Path(id): Path<Uuid>,
-    Json(request): Json<TokenizeAssetRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    // Simular busca do ativo no banco
-    let asset = Asset {
-        id,
-        name: "Ativo Exemplo".to_string(),
-        description: "Descrição".to_string(),
-        asset_type: crate::models::AssetType::RealEstate,
-        value: 1000000,
-        total_supply: request.total_supply,
-        available_supply: request.total_supply,
-        token_address: None,
-        owner: "0x123".to_string(),
-        metadata: crate::models::AssetMetadata {
-            location: Some("São Paulo".to_string()),
-            valuation_date: chrono::Utc::now(),
-            appraiser: None,
-            documents: vec![],
-            images: vec![],
-            additional_info: std::collections::HashMap::new(),
-        },
-        status: AssetStatus::Approved,
-        created_at: chrono::Utc::now(),
-        updated_at: chrono::Utc::now(),
+    payload: Result<Json<TokenizeAssetRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let asset = find_asset(&state, id).await?;
+
+    if asset.token_address.is_some() {
+        return Err(ApiError::Validation("Ativo já foi tokenizado".to_string()));
+    }
+    if asset.status == AssetStatus::Tokenizing {
+        return Err(tokenizing_elsewhere());
+    }
+    if asset.status != AssetStatus::Approved {
+        return Err(ApiError::Validation(format!(
+            "Apenas ativos aprovados podem ser tokenizados (status atual: {:?})",
+            asset.status
+        )));
+    }
+
+    let service = match request.chain.as_deref() {
+        Some(chain) => state
+            .networks
+            .resolve(chain)
+            .ok_or_else(|| ApiError::Validation(format!("Rede desconhecida: {}", chain)))?,
+        None => state.blockchain_service.clone(),
     };
 
-    match state.blockchain_service.deploy_asset_token(&request, &asset).await {
-        Ok(contract_address) => {
-            Ok(Json(json!({
-                "success": true,
-                "message": "Ativo tokenizado com sucesso",
-                "data": {
-                    "asset_id": id,
-                    "contract_address": contract_address,
-                    "symbol": request.symbol,
-                    "decimals": request.decimals,
-                    "total_supply": request.total_supply
-                }
-            })))
-        },
+    // Reserva o ativo antes do mint: só uma requisição passa de Approved
+    // para Tokenizing, as concorrentes recebem 409. Se o ativo mudou para
+    // outro status desde a leitura acima, vale o erro da transição.
+    let asset = match change_status(&state.db, id, AssetStatus::Tokenizing, &issuer.address, None).await {
+        Ok(asset) => asset,
+        Err(ApiError::Validation(message)) => {
+            return Err(match find_asset(&state, id).await?.status {
+                AssetStatus::Tokenizing => tokenizing_elsewhere(),
+                _ => ApiError::Validation(message),
+            });
+        }
+        Err(e) => return Err(e),
+    };
+
+    let token_uri = format!("{}/api/v1/assets/{}", state.config.server.public_url, asset.id);
+
+    let minted = service
+        .mint_asset(&asset, &token_uri)
+        .await
+        .map_err(ApiError::blockchain);
+    let minted = match minted {
+        Ok(minted) => minted,
+        // A transação pode ainda ser minerada: o ativo continua reservado e
+        // o indexador o vincula ao token quando o AssetMinted chegar.
+        Err(pending @ ApiError::OperationPending { .. }) => {
+            tracing::warn!("⏳ Mint do ativo {} sem confirmação: {}", id, pending);
+            return Err(pending);
+        }
         Err(e) => {
             tracing::error!("Erro ao tokenizar ativo: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            change_status(
+                &state.db,
+                id,
+                AssetStatus::Approved,
+                &issuer.address,
+                Some(format!("mint falhou: {}", e)),
+            )
+            .await?;
+            return Err(e);
         }
+    };
+
+    // O indexador pode ter vinculado o mint antes desta resposta.
+    let token_address = format!("{:?}", minted.contract_address);
+    let token_id = minted.token_id.to_string();
+    let (previous, asset) = AssetRepository::new(state.db.clone())
+        .modify(id, |asset| {
+            asset.token_address = Some(token_address.clone());
+            asset.token_id = Some(token_id.clone());
+            asset.chain_id = Some(service.chain_id());
+            if asset.status == AssetStatus::Tokenizing {
+                asset.status = AssetStatus::Tokenized;
+            }
+            Ok::<_, ApiError>(())
+        })
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if previous.status != asset.status {
+        AssetHistoryRepository::new(state.db.clone())
+            .record(&AssetStatusChange::new(
+                id,
+                Some(previous.status),
+                asset.status,
+                &issuer.address,
+                Some(format!("mint {:?}", minted.tx_hash)),
+            ))
+            .await?;
+    }
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "asset_id": id,
+            "chain_id": asset.chain_id,
+            "contract_address": asset.token_address,
+            "token_id": asset.token_id,
+            "tx_hash": format!("{:?}", minted.tx_hash),
+            "token_uri": token_uri,
+            "symbol": request.symbol,
+            "decimals": request.decimals,
+            "total_supply": request.total_supply
+        }),
+        "Ativo tokenizado com sucesso",
+    ))
+}
+
+async fn verify_asset(
+    State(state): State<AppState>,
+    _appraiser: RequireRole<roles::Appraiser>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let token_id = asset
+        .token_id
+        .as_deref()
+        .and_then(|token_id| ethers::types::U256::from_dec_str(token_id).ok())
+        .ok_or_else(|| ApiError::Validation("Ativo ainda não foi tokenizado".to_string()))?;
+
+    let tx_hash = asset_network(&state, &asset)?
+        .verify_asset(token_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Erro ao verificar ativo: {}", e);
+            ApiError::blockchain(e)
+        })?;
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "asset_id": id,
+            "token_id": asset.token_id,
+            "tx_hash": format!("{:?}", tx_hash)
+        }),
+        "Ativo verificado on-chain",
+    ))
+}
+
+/// Serviço da rede em que o ativo foi tokenizado; escritas on-chain do
+/// ativo vão sempre para ela.
+pub(super) fn asset_network(state: &AppState, asset: &Asset) -> Result<Arc<BlockchainService>, ApiError> {
+    let chain_id = asset
+        .chain_id
+        .ok_or_else(|| ApiError::Validation("Ativo ainda não foi tokenizado".to_string()))?;
+    state.networks.get(chain_id).ok_or_else(|| {
+        ApiError::Validation(format!("A rede {} do ativo não está configurada", chain_id))
+    })
+}
+
+/// Ativos excluídos pela API respondem 404.
+pub(super) async fn find_asset(state: &AppState, id: Uuid) -> Result<Asset, ApiError> {
+    AssetRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)
+}
+
+pub(super) fn require_owner(asset: &Asset, user: &AuthUser) -> Result<(), ApiError> {
+    if asset.owner.eq_ignore_ascii_case(&user.address) || user.has_role(Role::Admin) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden("apenas o proprietário do ativo".to_string()))
+    }
+}
+
+/// Envia o rascunho para avaliação.
+async fn submit_asset(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    require_owner(&find_asset(&state, id).await?, &user)?;
+
+    let asset = change_status(&state.db, id, AssetStatus::PendingApproval, &user.address, None).await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo enviado para aprovação",
+    ))
+}
+
+async fn approve_asset(
+    State(state): State<AppState>,
+    RequireRole(appraiser, _): RequireRole<roles::Appraiser>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let asset = change_status(&state.db, id, AssetStatus::Approved, &appraiser.address, None).await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo aprovado",
+    ))
+}
+
+/// Devolve o ativo ao proprietário como rascunho; o motivo é obrigatório.
+async fn reject_asset(
+    State(state): State<AppState>,
+    RequireRole(appraiser, _): RequireRole<roles::Appraiser>,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<AssetTransitionRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let reason = request
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty())
+        .ok_or_else(|| ApiError::Validation("Informe o motivo da rejeição".to_string()))?;
+
+    let asset = change_status(&state.db, id, AssetStatus::Draft, &appraiser.address, Some(reason)).await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo rejeitado",
+    ))
+}
+
+/// Tira o ativo de circulação; ativos com oferta ativa precisam tê-la
+/// cancelada antes. O corpo `{ "reason": ... }` é opcional.
+async fn retire_asset(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<AssetTransitionRequest>>,
+) -> Result<Json<Value>, ApiError> {
+    let reason = payload.and_then(|Json(request)| request.reason);
+    require_owner(&find_asset(&state, id).await?, &user)?;
+
+    let asset = change_status(&state.db, id, AssetStatus::Retired, &user.address, reason).await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(asset), &["value"]),
+        "Ativo retirado",
+    ))
+}
+
+async fn asset_history(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    find_asset(&state, id).await?;
+    let history = AssetHistoryRepository::new(state.db.clone())
+        .list_by_asset(id)
+        .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": history,
+        "count": history.len()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_asset, test_pool};
+
+    #[tokio::test]
+    async fn only_one_request_claims_the_mint() {
+        let db = test_pool().await;
+        let mut asset = test_asset("0xabc");
+        asset.status = AssetStatus::Approved;
+        AssetRepository::new(db.clone()).create(&asset).await.unwrap();
+
+        let claimed = change_status(&db, asset.id, AssetStatus::Tokenizing, "0xissuer", None)
+            .await
+            .unwrap();
+        assert_eq!(claimed.status, AssetStatus::Tokenizing);
+
+        let second = change_status(&db, asset.id, AssetStatus::Tokenizing, "0xissuer", None).await;
+        assert!(matches!(second, Err(ApiError::Validation(_))));
+
+        // Mint recusado: o ativo volta a ficar disponível.
+        let released = change_status(&db, asset.id, AssetStatus::Approved, "0xissuer", None)
+            .await
+            .unwrap();
+        assert_eq!(released.status, AssetStatus::Approved);
+        assert!(!AssetStatus::Approved.can_transition_to(AssetStatus::Tokenized));
     }
 }
@@ -0,0 +1,126 @@
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, siwe, AuthUser, Credential},
+    db::{RoleRepository, SessionRepository},
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{LoginRequest, Role, RoleGrant, Session},
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/nonce", get(create_nonce))
+        .route("/login", post(login))
+        .route("/session", get(current_session))
+        .route("/logout", post(logout))
+}
+
+/// Nonce de uso único para a mensagem SIWE, junto dos campos que o cliente
+/// precisa repetir nela.
+async fn create_nonce(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let nonce = auth::random_token(16);
+    let expires_at = crate::db::now() + chrono::Duration::seconds(state.config.auth.nonce_ttl_secs as i64);
+
+    SessionRepository::new(state.db.clone())
+        .create_nonce(&nonce, &expires_at)
+        .await?;
+
+    Ok(ApiResponse::success(json!({
+        "nonce": nonce,
+        "domain": state.config.auth.siwe_domain,
+        "uri": state.config.server.public_url,
+        "version": "1",
+        "chain_id": state.blockchain_service.chain_id(),
+        "expires_at": expires_at
+    })))
+}
+
+async fn login(
+    State(state): State<AppState>,
+    payload: Result<Json<LoginRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+
+    let message = siwe::verify(&request.message, &request.signature)?;
+    let now = crate::db::now();
+    let chain_ids: Vec<u64> = state.networks.all().iter().map(|network| network.chain_id()).collect();
+    message.validate(&state.config.auth.siwe_domain, &chain_ids, now)?;
+
+    let sessions = SessionRepository::new(state.db.clone());
+    if !sessions.consume_nonce(&message.nonce).await? {
+        tracing::warn!("🔒 Login SIWE recusado: nonce desconhecido, expirado ou já usado");
+        return Err(ApiError::Unauthorized);
+    }
+
+    // A sessão não sobrevive à validade declarada na própria mensagem.
+    let mut expires_at = now + chrono::Duration::seconds(state.config.auth.session_ttl_secs as i64);
+    if let Some(expiration) = message.expiration_time {
+        expires_at = expires_at.min(expiration);
+    }
+
+    let session = Session {
+        id: Uuid::new_v4(),
+        address: format!("{:?}", message.address),
+        chain_id: message.chain_id,
+        created_at: now,
+        expires_at,
+        revoked_at: None,
+    };
+    let token = auth::random_token(32);
+    sessions.create(&session, &auth::hash_token(&token)).await?;
+
+    // Primeiro login: toda carteira começa como investidora.
+    let roles = RoleRepository::new(state.db.clone());
+    if roles.roles_of(&session.address).await?.is_empty() {
+        roles
+            .grant(&RoleGrant {
+                address: session.address.clone(),
+                role: Role::Investor,
+                granted_by: None,
+                created_at: now,
+            })
+            .await?;
+    }
+
+    tracing::info!("🔐 Login SIWE: {} (chain {})", session.address, session.chain_id);
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "token": token,
+            "token_type": "Bearer",
+            "session": session,
+            "roles": roles.roles_of(&session.address).await?
+        }),
+        "Login realizado com sucesso",
+    ))
+}
+
+async fn current_session(user: AuthUser) -> Json<Value> {
+    ApiResponse::success(json!(user))
+}
+
+async fn logout(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<Value>, ApiError> {
+    let Credential::Session { id, .. } = user.credential else {
+        return Err(ApiError::Validation(
+            "Chaves de API são revogadas em /api/v1/api-keys".to_string(),
+        ));
+    };
+
+    SessionRepository::new(state.db.clone()).revoke(id).await?;
+
+    Ok(ApiResponse::success_with_message(
+        json!({ "session_id": id }),
+        "Sessão encerrada",
+    ))
+}
@@ -1,37 +1,90 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
-    routing::get,
+    routing::{get, post},
     Router,
 };
+use ethers::types::{Address, U256};
+use serde::Deserialize;
 use serde_json::{json, Value};
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
 
-use crate::AppState;
+use crate::{
+    auth::{roles, RequireRole},
+    db::{AssetRepository, TxOperationRepository},
+    handlers::{error::ApiError, response::ApiResponse},
+    services::blockchain::{BlockchainService, OPERATION_GAS},
+    units::{format_ether, format_gwei, format_units},
+    AppState,
+};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/status", get(blockchain_status))
         .route("/balance/:address", get(get_balance))
+        .route("/balance/:address/tokens", get(get_token_balances))
         .route("/block", get(get_latest_block))
         .route("/gas-price", get(get_gas_price))
+        .route("/operations", get(list_operations))
+        .route("/operations/:id", get(get_operation))
+        .route("/nonces/:nonce/cancel", post(cancel_nonce))
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ChainQuery {
+    /// Nome da rede ou chain id; sem ele vale a rede padrão.
+    pub chain: Option<String>,
+}
+
+pub(super) fn network(state: &AppState, query: &ChainQuery) -> Option<Arc<BlockchainService>> {
+    match query.chain.as_deref() {
+        Some(chain) => state.networks.resolve(chain),
+        None => Some(state.blockchain_service.clone()),
+    }
 }
 
-async fn blockchain_status(State(state): State<AppState>) -> Json<Value> {
-    let is_connected = state.blockchain_service.is_connected();
-    
-    let status = if is_connected {
-        match state.blockchain_service.get_block_number().await {
+/// Com `?chain=` devolve só a rede pedida; sem ele, todas as configuradas.
+async fn blockchain_status(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    if query.chain.is_some() {
+        let service = network(&state, &query)
+            .ok_or_else(|| ApiError::Validation("Rede desconhecida".to_string()))?;
+        return Ok(ApiResponse::success(network_status(&service).await));
+    }
+
+    let mut networks = Vec::new();
+    for service in state.networks.all() {
+        networks.push(network_status(service).await);
+    }
+
+    Ok(ApiResponse::success(json!({
+        "default_network": state.config.blockchain.default_network,
+        "networks": networks
+    })))
+}
+
+async fn network_status(service: &BlockchainService) -> Value {
+    let network = service.network();
+    let is_connected = service.is_connected();
+
+    let mut status = if is_connected {
+        let nonce = match service.nonce_status().await {
+            Ok(nonce) => json!(nonce),
+            Err(e) => json!({ "error": format!("Erro ao consultar nonce: {}", e) }),
+        };
+
+        match service.get_block_number().await {
             Ok(block_number) => json!({
                 "connected": true,
-                "chain_id": state.config.blockchain.chain_id,
-                "rpc_url": state.config.blockchain.rpc_url,
                 "latest_block": block_number.to_string(),
-                "contracts": {
-                    "asset_token": state.config.blockchain.contract_addresses.asset_token,
-                    "marketplace": state.config.blockchain.contract_addresses.marketplace,
-                    "waitlist": state.config.blockchain.contract_addresses.waitlist
-                }
+                "contracts": network.contract_addresses,
+                "nonce": nonce
             }),
             Err(e) => json!({
                 "connected": false,
@@ -45,28 +98,41 @@ async fn blockchain_status(State(state): State<AppState>) -> Json<Value> {
         })
     };
 
-    Json(json!({
-        "success": true,
-        "data": status
-    }))
+    status["health"] = json!(service.health());
+    status["subscriptions"] = match service.subscriptions() {
+        Some(subscriptions) => json!({
+            "ws_urls": network.ws_urls,
+            "live": subscriptions.is_live(),
+            "latest_head": subscriptions.latest_head()
+        }),
+        None => Value::Null,
+    };
+    status["network"] = json!(network.name);
+    status["chain_id"] = json!(network.chain_id);
+    status["rpc_urls"] = json!(network.rpc_urls);
+    status["rpc_quorum"] = json!(network.rpc_quorum);
+    status["rpc"] = json!(service.rpc_stats());
+    status
 }
 
 async fn get_balance(
     State(state): State<AppState>,
     Path(address): Path<String>,
+    Query(query): Query<ChainQuery>,
 ) -> Result<Json<Value>, StatusCode> {
     if !crate::services::blockchain::is_valid_address(&address) {
         return Err(StatusCode::BAD_REQUEST);
     }
+    let service = network(&state, &query).ok_or(StatusCode::BAD_REQUEST)?;
 
-    match state.blockchain_service.get_balance(&address).await {
+    match service.get_balance(&address).await {
         Ok(balance) => {
             Ok(Json(json!({
                 "success": true,
                 "data": {
                     "address": address,
                     "balance_wei": balance.to_string(),
-                    "balance_eth": format!("{:.6}", balance.as_u128() as f64 / 1e18)
+                    "balance_eth": format_ether(balance)
                 }
             })))
         },
@@ -77,8 +143,117 @@ async fn get_balance(
     }
 }
 
-async fn get_latest_block(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
-    match state.blockchain_service.get_block_number().await {
+/// Consultas `balanceOf`/`ownerOf` em andamento ao mesmo tempo.
+const BALANCE_CONCURRENCY: usize = 8;
+
+/// Ativos da plataforma em poder do endereço na rede pedida (`?chain=`, ou a
+/// padrão), consultados on-chain a partir dos contratos gravados em cada
+/// ativo tokenizado nela. Um contrato que não responde (endereço errado,
+/// contrato destruído) não derruba a consulta: o ativo aparece em `errors`.
+async fn get_token_balances(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let holder: Address = address
+        .parse()
+        .map_err(|_| ApiError::Validation("Endereço inválido".to_string()))?;
+    let service = network(&state, &query)
+        .ok_or_else(|| ApiError::Validation("Rede desconhecida".to_string()))?;
+
+    let mut errors = Vec::new();
+    let mut tokens = Vec::new();
+    for asset in AssetRepository::new(state.db.clone())
+        .list_tokenized(service.chain_id())
+        .await?
+    {
+        let contract = asset.token_address.as_deref().and_then(|a| a.parse::<Address>().ok());
+        let token_id = asset.token_id.as_deref().map(U256::from_dec_str).transpose();
+        match (contract, token_id) {
+            (Some(contract), Ok(token_id)) => tokens.push((asset, contract, token_id)),
+            _ => errors.push(json!({ "asset_id": asset.id, "error": "token gravado inválido" })),
+        }
+    }
+
+    // Um `balanceOf` por contrato ERC-721 evita um `ownerOf` por ativo
+    // quando o endereço não tem nenhum NFT daquele contrato.
+    let nft_contracts: HashSet<Address> = tokens
+        .iter()
+        .filter(|(_, _, token_id)| token_id.is_some())
+        .map(|(_, contract, _)| *contract)
+        .collect();
+    let holds_nfts: HashMap<Address, Result<bool, String>> = stream::iter(nft_contracts)
+        .map(|contract| {
+            let service = service.clone();
+            async move {
+                let count = service.nft_balance(contract, holder).await.map_err(|e| e.to_string());
+                (contract, count.map(|count| !count.is_zero()))
+            }
+        })
+        .buffer_unordered(BALANCE_CONCURRENCY)
+        .collect()
+        .await;
+
+    let balances: Vec<_> = stream::iter(tokens)
+        .map(|(asset, contract, token_id)| {
+            let service = service.clone();
+            let holds_any = token_id.map(|_| holds_nfts[&contract].clone());
+            async move {
+                let balance = match holds_any {
+                    Some(Err(e)) => Err(e),
+                    Some(Ok(false)) => return (asset, Ok(None)),
+                    Some(Ok(true)) | None => service
+                        .get_token_balance(contract, holder, token_id)
+                        .await
+                        .map_err(|e| e.to_string()),
+                };
+                (asset, balance.map(Some))
+            }
+        })
+        .buffered(BALANCE_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut holdings = Vec::new();
+    for (asset, balance) in balances {
+        let balance = match balance {
+            Ok(Some(balance)) if !balance.balance.is_zero() => balance,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::warn!("⚠️ Saldo de {} no ativo {} indisponível: {}", address, asset.id, e);
+                errors.push(json!({ "asset_id": asset.id, "error": e }));
+                continue;
+            }
+        };
+
+        holdings.push(json!({
+            "asset_id": asset.id,
+            "name": asset.name,
+            "token_address": asset.token_address,
+            "token_id": asset.token_id,
+            "standard": balance.standard,
+            "balance": balance.balance.to_string(),
+            "balance_formatted": format_units(balance.balance, balance.decimals),
+            "decimals": balance.decimals
+        }));
+    }
+
+    Ok(ApiResponse::success(json!({
+        "address": address,
+        "chain_id": service.chain_id(),
+        "count": holdings.len(),
+        "holdings": holdings,
+        "errors": errors
+    })))
+}
+
+async fn get_latest_block(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let service = network(&state, &query).ok_or(StatusCode::BAD_REQUEST)?;
+
+    match service.get_block_number().await {
         Ok(block_number) => {
             Ok(Json(json!({
                 "success": true,
@@ -95,16 +270,128 @@ async fn get_latest_block(State(state): State<AppState>) -> Result<Json<Value>,
     }
 }
 
-async fn get_gas_price(State(_state): State<AppState>) -> Json<Value> {
-    // Em produção, obter o preço real do gas
-    let gas_price = "20000000000"; // 20 gwei simulado
+/// Preço de gas atual e custo estimado das operações da plataforma. Com
+/// EIP-1559 o custo de cada faixa usa `base fee + gorjeta`; o `max_fee`
+/// sugerido cobre a base fee dobrando por alguns blocos.
+async fn get_gas_price(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let service = network(&state, &query)
+        .ok_or_else(|| ApiError::Validation("Rede desconhecida".to_string()))?;
+    let estimate = service.fee_estimate().await.map_err(|e| {
+        tracing::error!("Erro ao obter preço do gas: {}", e);
+        ApiError::Blockchain(e.to_string())
+    })?;
 
-    Json(json!({
-        "success": true,
-        "data": {
-            "gas_price_wei": gas_price,
-            "gas_price_gwei": "20",
-            "timestamp": chrono::Utc::now().to_rfc3339()
+    let tiers: Vec<(&str, U256, Option<U256>)> = match estimate.base_fee_per_gas {
+        Some(base_fee) => ["slow", "standard", "fast"]
+            .into_iter()
+            .zip(estimate.priority_fees)
+            .map(|(tier, priority_fee)| (tier, base_fee + priority_fee, Some(priority_fee)))
+            .collect(),
+        None => vec![("standard", estimate.gas_price, None)],
+    };
+
+    let mut fees = serde_json::Map::new();
+    for (tier, effective, priority_fee) in &tiers {
+        fees.insert(tier.to_string(), match (priority_fee, estimate.base_fee_per_gas) {
+            (Some(priority_fee), Some(base_fee)) => {
+                let max_fee = base_fee * U256::from(2) + *priority_fee;
+                json!({
+                    "max_priority_fee_per_gas_wei": priority_fee.to_string(),
+                    "max_priority_fee_per_gas_gwei": format_gwei(*priority_fee),
+                    "max_fee_per_gas_wei": max_fee.to_string(),
+                    "max_fee_per_gas_gwei": format_gwei(max_fee),
+                    "effective_gas_price_wei": effective.to_string()
+                })
+            }
+            _ => json!({
+                "gas_price_wei": effective.to_string(),
+                "gas_price_gwei": format_gwei(*effective)
+            }),
+        });
+    }
+
+    let mut costs = serde_json::Map::new();
+    for (operation, gas) in OPERATION_GAS {
+        let mut cost = serde_json::Map::new();
+        cost.insert("gas".to_string(), json!(gas));
+        for (tier, effective, _) in &tiers {
+            let wei = effective * U256::from(gas);
+            cost.insert(tier.to_string(), json!({
+                "wei": wei.to_string(),
+                "eth": format_ether(wei)
+            }));
         }
-    }))
+        costs.insert(operation.to_string(), Value::Object(cost));
+    }
+
+    Ok(ApiResponse::success(json!({
+        "block_number": estimate.block_number,
+        "eip1559": estimate.base_fee_per_gas.is_some(),
+        "gas_price_wei": estimate.gas_price.to_string(),
+        "gas_price_gwei": format_gwei(estimate.gas_price),
+        "base_fee_per_gas_wei": estimate.base_fee_per_gas.map(|fee| fee.to_string()),
+        "base_fee_per_gas_gwei": estimate.base_fee_per_gas.map(format_gwei),
+        "fees": fees,
+        "estimated_costs": costs,
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    })))
+}
+
+async fn list_operations(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let operations = TxOperationRepository::new(state.db.clone()).list().await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": operations,
+        "count": operations.len()
+    })))
+}
+
+/// Operação com todas as tentativas enviadas para o seu nonce.
+async fn get_operation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let operations = TxOperationRepository::new(state.db.clone());
+    let operation = operations.find_by_id(id).await?.ok_or(ApiError::NotFound)?;
+    let attempts = operations.attempts(id).await?;
+
+    Ok(ApiResponse::success(json!({
+        "operation": operation,
+        "attempts": attempts
+    })))
+}
+
+/// Substitui o nonce pendente por uma transferência de valor zero para a
+/// própria carteira de operação da rede (`?chain=`, ou a padrão).
+async fn cancel_nonce(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    Path(nonce): Path<u64>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let service = network(&state, &query)
+        .ok_or_else(|| ApiError::Validation("Rede desconhecida".to_string()))?;
+    tracing::info!(
+        "🛑 Cancelando nonce {} em {} (solicitado por {})",
+        nonce,
+        service.network().name,
+        admin.address
+    );
+
+    let operation = service
+        .cancel_nonce(nonce)
+        .await
+        .map_err(|e| {
+            tracing::error!("Erro ao cancelar nonce {}: {}", nonce, e);
+            ApiError::Blockchain(e.to_string())
+        })?;
+
+    Ok(ApiResponse::success_with_message(
+        json!(operation),
+        "Cancelamento enviado, aguardando mineração",
+    ))
 }
@@ -0,0 +1,391 @@
+use axum::{
+    extract::{
+        multipart::{Field, MultipartError, MultipartRejection},
+        DefaultBodyLimit, Multipart, Path, State,
+    },
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use ethers::utils::hex;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::assets::{find_asset, require_owner};
+use crate::{
+    auth::{AuthUser, MaybeAuthUser},
+    db::DocumentRepository,
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{Asset, AssetDocument, AssetStatus, DocumentKind, Role},
+    AppState,
+};
+
+/// Tamanho máximo dos campos de texto do formulário (`kind`).
+const MAX_TEXT_FIELD_BYTES: usize = 64;
+
+/// Arquivos anexados aos ativos, sob `/assets/:id/documents`.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/:id/documents",
+            // O limite padrão do axum (2 MB) é trocado pelo `UPLOAD_MAX_BYTES`,
+            // conferido enquanto o arquivo é lido.
+            get(list_documents)
+                .post(upload_document)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/:id/documents/:document_id",
+            get(download_document).delete(delete_document),
+        )
+}
+
+/// Dono, avaliadores e admins veem todos os documentos; os demais, só as
+/// imagens de ativos já aprovados.
+async fn list_documents(
+    State(state): State<AppState>,
+    MaybeAuthUser(user): MaybeAuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let asset = find_asset(&state, id).await?;
+    let documents: Vec<AssetDocument> = DocumentRepository::new(state.db.clone())
+        .list_by_asset(id)
+        .await?
+        .into_iter()
+        .filter(|document| can_read(&asset, document, user.as_ref()))
+        .collect();
+
+    Ok(Json(json!({
+        "success": true,
+        "data": documents,
+        "count": documents.len()
+    })))
+}
+
+/// `multipart/form-data` com os campos `kind` (`Deed`, `Appraisal`,
+/// `Insurance`, `Image` ou `Other`) e `file`.
+async fn upload_document(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let mut multipart = multipart.map_err(|e| ApiError::BadRequest(e.body_text()))?;
+    let asset = editable_asset(&state, id, &user).await?;
+    let max_bytes = state.config.storage.max_upload_bytes;
+
+    let mut kind = None;
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        match field.name() {
+            Some("kind") => {
+                let raw = read_field(field, MAX_TEXT_FIELD_BYTES).await?;
+                let raw = String::from_utf8_lossy(&raw).trim().to_string();
+                kind = Some(
+                    serde_json::from_value::<DocumentKind>(Value::String(raw.clone())).map_err(|_| {
+                        ApiError::Validation(format!(
+                            "kind '{}' inválido; use Deed, Appraisal, Insurance, Image ou Other",
+                            raw
+                        ))
+                    })?,
+                );
+            }
+            Some("file") if file.is_none() => {
+                let file_name = field.file_name().map(clean_file_name).unwrap_or_default();
+                let declared = field.content_type().map(str::to_string);
+                let bytes = read_field(field, max_bytes).await?;
+                file = Some((file_name, declared, bytes));
+            }
+            Some("file") => {
+                return Err(ApiError::BadRequest("Envie um arquivo por requisição".to_string()));
+            }
+            other => {
+                return Err(ApiError::BadRequest(format!(
+                    "Campo inesperado '{}'",
+                    other.unwrap_or_default()
+                )));
+            }
+        }
+    }
+
+    let kind = kind.ok_or_else(|| ApiError::Validation("Campo 'kind' é obrigatório".to_string()))?;
+    let (file_name, declared, bytes) =
+        file.ok_or_else(|| ApiError::Validation("Campo 'file' é obrigatório".to_string()))?;
+    if bytes.is_empty() {
+        return Err(ApiError::Validation("Arquivo vazio".to_string()));
+    }
+
+    let content_type = sniff_content_type(&bytes).ok_or_else(|| {
+        ApiError::Validation("Tipo de arquivo não permitido; aceitos: PDF, JPEG, PNG e WebP".to_string())
+    })?;
+    if let Some(declared) = declared.filter(|declared| declared != "application/octet-stream") {
+        if declared != content_type {
+            return Err(ApiError::Validation(format!(
+                "Arquivo enviado como {}, mas o conteúdo é {}",
+                declared, content_type
+            )));
+        }
+    }
+    if kind == DocumentKind::Image && !content_type.starts_with("image/") {
+        return Err(ApiError::Validation("Imagens devem ser JPEG, PNG ou WebP".to_string()));
+    }
+
+    let document_id = Uuid::new_v4();
+    let document = AssetDocument {
+        id: document_id,
+        asset_id: asset.id,
+        kind,
+        file_name: if file_name.is_empty() { document_id.to_string() } else { file_name },
+        content_type: content_type.to_string(),
+        size: bytes.len() as u64,
+        sha256: hex::encode(Sha256::digest(&bytes)),
+        storage_key: format!("assets/{}/{}", asset.id, document_id),
+        uploaded_by: user.address.clone(),
+        created_at: crate::db::now(),
+    };
+
+    state
+        .storage
+        .put(&document.storage_key, &document.content_type, bytes)
+        .await?;
+    if let Err(e) = DocumentRepository::new(state.db.clone()).create(&document).await {
+        // Sem o registro, o arquivo ficaria órfão.
+        let _ = state.storage.delete(&document.storage_key).await;
+        return Err(e.into());
+    }
+
+    tracing::info!(
+        "📎 {} enviou {:?} '{}' ({} bytes) para o ativo {}",
+        user.address,
+        document.kind,
+        document.file_name,
+        document.size,
+        asset.id
+    );
+
+    Ok(ApiResponse::success_with_message(json!(document), "Documento enviado"))
+}
+
+/// Devolve o arquivo como foi enviado, depois de conferir o SHA-256.
+async fn download_document(
+    State(state): State<AppState>,
+    MaybeAuthUser(user): MaybeAuthUser,
+    Path((id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let asset = find_asset(&state, id).await?;
+    let document = DocumentRepository::new(state.db.clone())
+        .find(id, document_id)
+        .await?
+        // Quem não pode ler o documento não fica sabendo que ele existe.
+        .filter(|document| can_read(&asset, document, user.as_ref()))
+        .ok_or(ApiError::NotFound)?;
+
+    let bytes = state.storage.get(&document.storage_key).await?;
+    if hex::encode(Sha256::digest(&bytes)) != document.sha256 {
+        tracing::error!(
+            "SHA-256 do documento {} não confere com o registrado; arquivo alterado no armazenamento",
+            document.id
+        );
+        return Err(ApiError::InternalServer);
+    }
+
+    Ok((
+        [
+            (CONTENT_TYPE, document.content_type.clone()),
+            (CONTENT_DISPOSITION, content_disposition(&document.file_name)),
+            (ETAG, format!("\"{}\"", document.sha256)),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+async fn delete_document(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, ApiError> {
+    editable_asset(&state, id, &user).await?;
+    let documents = DocumentRepository::new(state.db.clone());
+    let document = documents
+        .find(id, document_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    documents.delete(document.id).await?;
+    if let Err(e) = state.storage.delete(&document.storage_key).await {
+        tracing::warn!("Arquivo {} não foi removido do armazenamento: {}", document.storage_key, e);
+    }
+
+    tracing::info!("📎 {} removeu o documento {} do ativo {}", user.address, document.id, id);
+
+    Ok(ApiResponse::success_with_message(json!({ "id": document.id }), "Documento removido"))
+}
+
+/// Documentos fazem parte do que o avaliador aprova; como os demais dados do
+/// ativo, só mudam em `Draft` ou `PendingApproval`.
+async fn editable_asset(state: &AppState, id: Uuid, user: &AuthUser) -> Result<Asset, ApiError> {
+    let asset = find_asset(state, id).await?;
+    require_owner(&asset, user)?;
+
+    if !matches!(asset.status, AssetStatus::Draft | AssetStatus::PendingApproval) {
+        return Err(ApiError::Validation(format!(
+            "Documentos de ativos em {:?} não podem ser alterados",
+            asset.status
+        )));
+    }
+
+    Ok(asset)
+}
+
+/// Escrituras, laudos e apólices ficam com o dono, os avaliadores e os
+/// admins. Imagens de ativos aprovados (e ainda não retirados) são públicas.
+fn can_read(asset: &Asset, document: &AssetDocument, user: Option<&AuthUser>) -> bool {
+    let privileged = user.is_some_and(|user| {
+        asset.owner.eq_ignore_ascii_case(&user.address) || user.has_role(Role::Appraiser)
+    });
+    if privileged {
+        return true;
+    }
+
+    document.kind == DocumentKind::Image && is_public(asset.status)
+}
+
+fn is_public(status: AssetStatus) -> bool {
+    !matches!(
+        status,
+        AssetStatus::Draft | AssetStatus::PendingApproval | AssetStatus::Retired
+    )
+}
+
+/// Lê o campo inteiro, recusando-o assim que passar de `max_bytes`.
+async fn read_field(mut field: Field<'_>, max_bytes: usize) -> Result<Vec<u8>, ApiError> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(ApiError::Validation(format!(
+                "Campo '{}' excede o limite de {} bytes",
+                field.name().unwrap_or_default(),
+                max_bytes
+            )));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn multipart_error(err: MultipartError) -> ApiError {
+    ApiError::BadRequest(err.body_text())
+}
+
+/// Tipos aceitos, reconhecidos pelos primeiros bytes do arquivo e não pelo
+/// Content-Type que o cliente declarou.
+fn sniff_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Apenas o nome final, sem diretórios nem caracteres de controle.
+fn clean_file_name(raw: &str) -> String {
+    raw.rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// `attachment` com o nome em ASCII e, para os demais caracteres, a forma
+/// `filename*` (RFC 6266).
+fn content_disposition(file_name: &str) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' { c } else { '_' })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", ascii, encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auth::Credential, db::test_asset};
+
+    fn user(address: &str, roles: Vec<Role>) -> AuthUser {
+        AuthUser {
+            address: address.to_string(),
+            roles,
+            credential: Credential::Session {
+                id: Uuid::new_v4(),
+                expires_at: crate::db::now(),
+            },
+        }
+    }
+
+    fn document(asset: &Asset, kind: DocumentKind) -> AssetDocument {
+        AssetDocument {
+            id: Uuid::new_v4(),
+            asset_id: asset.id,
+            kind,
+            file_name: "arquivo".to_string(),
+            content_type: "image/png".to_string(),
+            size: 1,
+            sha256: String::new(),
+            storage_key: format!("assets/{}/x", asset.id),
+            uploaded_by: asset.owner.clone(),
+            created_at: crate::db::now(),
+        }
+    }
+
+    #[test]
+    fn only_images_of_approved_assets_are_public() {
+        let mut asset = test_asset("0xowner");
+        let deed = document(&asset, DocumentKind::Deed);
+        let image = document(&asset, DocumentKind::Image);
+        let stranger = user("0xother", vec![Role::Investor]);
+
+        for status in [AssetStatus::Draft, AssetStatus::PendingApproval, AssetStatus::Retired] {
+            asset.status = status;
+            assert!(!can_read(&asset, &image, None));
+            assert!(!can_read(&asset, &image, Some(&stranger)));
+        }
+
+        asset.status = AssetStatus::Trading;
+        assert!(can_read(&asset, &image, None));
+        assert!(can_read(&asset, &image, Some(&stranger)));
+        assert!(!can_read(&asset, &deed, None));
+        assert!(!can_read(&asset, &deed, Some(&stranger)));
+    }
+
+    #[test]
+    fn owner_appraisers_and_admins_read_every_document() {
+        let asset = test_asset("0xOwner");
+        let deed = document(&asset, DocumentKind::Deed);
+
+        assert!(can_read(&asset, &deed, Some(&user("0xowner", vec![]))));
+        assert!(can_read(&asset, &deed, Some(&user("0xappraiser", vec![Role::Appraiser]))));
+        assert!(can_read(&asset, &deed, Some(&user("0xadmin", vec![Role::Admin]))));
+        assert!(!can_read(&asset, &deed, Some(&user("0xissuer", vec![Role::Issuer]))));
+    }
+}
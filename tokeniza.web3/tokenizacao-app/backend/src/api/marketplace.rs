@@ -1,15 +1,28 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{rejection::JsonRejection, Path, Query, State},
     response::Json,
     routing::{get, post},
     Router,
 };
+use ethers::types::{Address, H256, U256};
 use serde_json::{json, Value};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    models::{CreateListingRequest, PurchaseRequest},
+    api::{
+        assets::{apply_onchain_status, asset_network},
+        blockchain::{network, ChainQuery},
+    },
+    auth::{roles, AuthUser, RequireRole},
+    db::{AssetRepository, Db, ListingRepository, TransactionRepository},
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{
+        Asset, AssetStatus, CreateListingRequest, Listing, ListingStatus, PrepareListingRequest,
+        PreparePurchaseRequest, PurchaseRequest, Role, SignedTxRequest, Transaction, TransactionStatus, UpdateFeeRequest,
+    },
+    services::{blockchain::BlockchainService, contracts::MarketplaceEvents, indexer::EffectWriter},
+    units::{self, with_ether, Wei},
     AppState,
 };
 
@@ -17,148 +30,668 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/listings", get(list_listings))
         .route("/listings", post(create_listing))
+        .route("/listings/prepare", post(prepare_listing))
         .route("/listings/:id", get(get_listing))
+        .route("/listings/:id/cancel/prepare", post(prepare_cancel_listing))
+        .route("/listings/:id/cancel", post(cancel_listing))
+        .route("/purchase/prepare", post(prepare_purchase))
         .route("/purchase", post(purchase_tokens))
         .route("/transactions", get(list_transactions))
+        .route("/transactions/:reference", get(get_transaction))
+        .route("/fees", get(get_fees).put(update_fee))
+        .route("/fees/withdraw", post(withdraw_fees))
 }
 
-async fn list_listings(State(_state): State<AppState>) -> Json<Value> {
-    let listings = vec![
-        json!({
-            "id": Uuid::new_v4(),
-            "asset_id": Uuid::new_v4(),
-            "seller": "0x1234567890123456789012345678901234567890",
-            "price": "1000000000000000000", // 1 ETH em wei
-            "quantity": 100,
-            "status": "Active",
-            "created_at": chrono::Utc::now().to_rfc3339()
-        }),
-        json!({
-            "id": Uuid::new_v4(),
-            "asset_id": Uuid::new_v4(),
-            "seller": "0x0987654321098765432109876543210987654321",
-            "price": "500000000000000000", // 0.5 ETH em wei
-            "quantity": 50,
-            "status": "Active",
-            "created_at": chrono::Utc::now().to_rfc3339()
-        })
-    ];
+/// Teto de `Marketplace.updateMarketplaceFee` (10%).
+const MAX_FEE_BPS: u64 = 1000;
+
+async fn list_listings(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let listings = ListingRepository::new(state.db.clone()).list().await?;
 
-    Json(json!({
+    Ok(Json(json!({
         "success": true,
-        "data": listings,
+        "data": with_ether(json!(listings), &["price"]),
         "count": listings.len()
-    }))
+    })))
 }
 
-async fn create_listing(
-    State(_state): State<AppState>,
-    Json(request): Json<CreateListingRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    let listing_id = Uuid::new_v4();
-    
-    tracing::info!("📋 Criando nova oferta: {} tokens por {} wei", request.quantity, request.price);
-
-    // Em produção, validar se o vendedor possui os tokens
-    // e salvar no banco de dados
-    
-    let listing = json!({
-        "id": listing_id,
-        "asset_id": request.asset_id,
-        "price": request.price,
-        "quantity": request.quantity,
-        "status": "Active",
-        "created_at": chrono::Utc::now().to_rfc3339(),
-        "expires_at": request.expires_at
-    });
+/// Transações que a carteira dona do NFT assina para listá-lo: o `approve`
+/// do Marketplace, quando necessário, e o `listAsset`.
+async fn prepare_listing(
+    State(state): State<AppState>,
+    user: AuthUser,
+    payload: Result<Json<PrepareListingRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let price = listing_price(&request)?;
+    if price.is_zero() {
+        return Err(ApiError::Validation("Preço deve ser maior que zero".to_string()));
+    }
 
-    Ok(Json(json!({
-        "success": true,
-        "message": "Oferta criada com sucesso",
-        "data": listing
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(request.asset_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if !asset.owner.eq_ignore_ascii_case(&user.address) {
+        return Err(ApiError::Forbidden("apenas o proprietário do ativo".to_string()));
+    }
+    if !asset.status.can_transition_to(AssetStatus::Trading) {
+        return Err(ApiError::Validation(format!(
+            "Ativo não pode ser listado no status {:?}",
+            asset.status
+        )));
+    }
+    let (service, nft_contract, token_id) = onchain_token(&state, &asset)?;
+    let seller = session_wallet(&user)?;
+
+    let holder = service
+        .nft_owner(nft_contract, token_id)
+        .await
+        .map_err(blockchain_error("consultar o dono do NFT"))?;
+    if holder != seller {
+        return Err(ApiError::Validation(format!(
+            "O NFT está na carteira {:?}; apenas ela pode listá-lo",
+            holder
+        )));
+    }
+
+    let transactions = service
+        .prepare_listing(seller, nft_contract, token_id, price.into())
+        .await
+        .map_err(blockchain_error("preparar a oferta"))?;
+
+    Ok(ApiResponse::success(json!({
+        "asset_id": asset.id,
+        "price": price,
+        "price_eth": units::format_ether(price.into()),
+        "transactions": transactions
     })))
 }
 
+/// Registra a oferta depois que o `listAsset` assinado pelo vendedor foi
+/// minerado. Preço e vendedor vêm do evento `AssetListed`.
+async fn create_listing(
+    State(state): State<AppState>,
+    user: AuthUser,
+    payload: Result<Json<CreateListingRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    if request.quantity == 0 || request.quantity > i64::MAX as u64 {
+        return Err(ApiError::Validation("Quantidade fora do intervalo permitido".to_string()));
+    }
+
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(request.asset_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if !asset.owner.eq_ignore_ascii_case(&user.address) {
+        return Err(ApiError::Forbidden("apenas o proprietário do ativo".to_string()));
+    }
+    let (service, nft_contract, token_id) = onchain_token(&state, &asset)?;
+
+    let mined = marketplace_events(&service, &request.tx_hash).await?;
+    let (log_index, listed) = mined
+        .events
+        .into_iter()
+        .find_map(|(log_index, event)| match event {
+            MarketplaceEvents::AssetListedFilter(event)
+                if event.nft_contract == nft_contract && event.token_id == token_id =>
+            {
+                Some((log_index, event))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| ApiError::Validation("A transação não lista este ativo".to_string()))?;
+    let seller = format!("{:?}", listed.seller);
+    if !seller.eq_ignore_ascii_case(&user.address) {
+        return Err(ApiError::Forbidden("a oferta foi criada por outra carteira".to_string()));
+    }
+    let price = Wei::from(listed.price);
+
+    tracing::info!(
+        "📋 Registrando oferta: {} tokens por {} ETH",
+        request.quantity,
+        units::format_ether(listed.price)
+    );
+
+    // As escritas entram no diário do indexador na posição do AssetListed,
+    // para que um reorg que descarte a transação também desfaça a oferta.
+    let tx = Db::begin(&state.db).await?;
+    let mut writer = EffectWriter::new(tx.clone(), service.chain_id(), mined.block_number, log_index);
+
+    // O indexador pode ter lido o AssetListed antes desta requisição; nesse
+    // caso a oferta já existe e só recebe os dados que não estão on-chain.
+    let listing = match ListingRepository::new(&tx).find_by_tx_hash(&mined.tx_hash).await? {
+        Some(listing) if !matches!(listing.status, ListingStatus::Active) => {
+            return Err(ApiError::Conflict("Essa oferta já foi encerrada".to_string()));
+        }
+        Some(previous) => {
+            let mut listing = previous.clone();
+            listing.seller = seller;
+            listing.quantity = request.quantity;
+            listing.expires_at = request.expires_at;
+            writer.update_listing(&previous, &listing).await?;
+            listing
+        }
+        None => {
+            if !asset.status.can_transition_to(AssetStatus::Trading) {
+                return Err(ApiError::Validation(format!(
+                    "Ativo não pode ser listado no status {:?}",
+                    asset.status
+                )));
+            }
+            let listing = Listing {
+                id: Uuid::new_v4(),
+                asset_id: asset.id,
+                seller,
+                price,
+                quantity: request.quantity,
+                status: ListingStatus::Active,
+                tx_hash: Some(mined.tx_hash),
+                created_at: crate::db::now(),
+                expires_at: request.expires_at,
+            };
+            writer.create_listing(&listing).await?;
+            listing
+        }
+    };
+
+    // Idem para o status do ativo.
+    apply_onchain_status(
+        &mut writer,
+        asset.id,
+        AssetStatus::Trading,
+        &listing.seller,
+        Some(format!("oferta {}", listing.id)),
+    )
+    .await?;
+    drop(writer);
+    tx.commit().await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(listing), &["price"]),
+        "Oferta criada com sucesso",
+    ))
+}
+
 async fn get_listing(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Value>, StatusCode> {
-    let listing = json!({
-        "id": id,
-        "asset_id": Uuid::new_v4(),
-        "seller": "0x1234567890123456789012345678901234567890",
-        "price": "1000000000000000000",
-        "quantity": 100,
-        "status": "Active",
-        "created_at": chrono::Utc::now().to_rfc3339()
-    });
+) -> Result<Json<Value>, ApiError> {
+    let listing = ListingRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
-    Ok(Json(json!({
-        "success": true,
-        "data": listing
+    Ok(ApiResponse::success(with_ether(json!(listing), &["price"])))
+}
+
+/// O `cancelListing` que o vendedor assina; o contrato só aceita a
+/// carteira que criou a oferta.
+async fn prepare_cancel_listing(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Value>, ApiError> {
+    let listing = ListingRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if !listing.seller.eq_ignore_ascii_case(&user.address) {
+        return Err(ApiError::Forbidden("apenas o vendedor da oferta".to_string()));
+    }
+    if !matches!(listing.status, ListingStatus::Active) {
+        return Err(ApiError::Validation("Oferta não está ativa".to_string()));
+    }
+    if TransactionRepository::new(state.db.clone())
+        .has_pending_for_listing(listing.id)
+        .await?
+    {
+        return Err(ApiError::Validation("Há uma compra em andamento para esta oferta".to_string()));
+    }
+
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(listing.asset_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let (service, nft_contract, token_id) = onchain_token(&state, &asset)?;
+
+    let transaction = service
+        .prepare_cancel_listing(session_wallet(&user)?, nft_contract, token_id)
+        .map_err(blockchain_error("preparar o cancelamento"))?;
+
+    Ok(ApiResponse::success(json!({
+        "listing_id": listing.id,
+        "transactions": [transaction]
+    })))
+}
+
+/// Registra o cancelamento depois que o `cancelListing` foi minerado.
+async fn cancel_listing(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<SignedTxRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let listing = ListingRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    if !listing.seller.eq_ignore_ascii_case(&user.address) && !user.has_role(Role::Admin) {
+        return Err(ApiError::Forbidden("apenas o vendedor da oferta".to_string()));
+    }
+
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(listing.asset_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let (service, nft_contract, token_id) = onchain_token(&state, &asset)?;
+
+    let mined = marketplace_events(&service, &request.tx_hash).await?;
+    let log_index = mined
+        .events
+        .iter()
+        .find_map(|(log_index, event)| match event {
+            MarketplaceEvents::ListingCancelledFilter(event)
+                if event.nft_contract == nft_contract
+                    && event.token_id == token_id
+                    && format!("{:?}", event.seller).eq_ignore_ascii_case(&listing.seller) =>
+            {
+                Some(*log_index)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| ApiError::Validation("A transação não cancela esta oferta".to_string()))?;
+
+    let tx = Db::begin(&state.db).await?;
+    let mut writer = EffectWriter::new(tx.clone(), service.chain_id(), mined.block_number, log_index);
+
+    // O indexador pode já ter aplicado o ListingCancelled.
+    let mut listing = ListingRepository::new(&tx)
+        .find_by_id(id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if matches!(listing.status, ListingStatus::Active) {
+        let previous = listing.clone();
+        listing.status = ListingStatus::Cancelled;
+        writer.update_listing(&previous, &listing).await?;
+    }
+    apply_onchain_status(
+        &mut writer,
+        asset.id,
+        AssetStatus::Tokenized,
+        &user.address,
+        Some(format!("oferta {} cancelada", listing.id)),
+    )
+    .await?;
+    drop(writer);
+    tx.commit().await?;
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "listing": with_ether(json!(listing), &["price"]),
+            "tx_hash": mined.tx_hash
+        }),
+        "Oferta cancelada",
+    ))
+}
+
+/// O `buyAsset` que a carteira do comprador assina e paga.
+async fn prepare_purchase(
+    State(state): State<AppState>,
+    user: AuthUser,
+    payload: Result<Json<PreparePurchaseRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let (listing, service, nft_contract, token_id) =
+        purchasable_listing(&state, request.listing_id, &user).await?;
+
+    if TransactionRepository::new(state.db.clone())
+        .has_pending_for_listing(listing.id)
+        .await?
+    {
+        return Err(ApiError::Validation("Já existe uma compra em andamento para esta oferta".to_string()));
+    }
+
+    let transaction = service
+        .prepare_purchase(session_wallet(&user)?, nft_contract, token_id, listing.price.into())
+        .map_err(blockchain_error("preparar a compra"))?;
+
+    Ok(ApiResponse::success(json!({
+        "listing_id": listing.id,
+        "price": listing.price,
+        "price_eth": units::format_ether(listing.price.into()),
+        "transactions": [transaction]
     })))
 }
 
+/// Registra a compra que a carteira da sessão enviou. A transação precisa
+/// ser um `buyAsset` desta oferta, da carteira da sessão e pagando o preço;
+/// a oferta e o ativo só mudam quando o TxTracker confirmar o recibo.
 async fn purchase_tokens(
     State(state): State<AppState>,
-    Json(request): Json<PurchaseRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    tracing::info!("💰 Processando compra: {} tokens para {}", request.quantity, request.buyer);
-
-    // Validar se o comprador tem fundos suficientes
-    if !crate::services::blockchain::is_valid_address(&request.buyer) {
-        return Err(StatusCode::BAD_REQUEST);
-    }
-
-    // Simular transação na blockchain
-    match state.blockchain_service.transfer_tokens(
-        "0x1234567890123456789012345678901234567890", // contract_address
-        "0x0987654321098765432109876543210987654321", // seller
-        &request.buyer,
-        ethers::types::U256::from(request.quantity),
-    ).await {
-        Ok(tx_hash) => {
-            let transaction = json!({
-                "id": Uuid::new_v4(),
-                "listing_id": request.listing_id,
-                "buyer": request.buyer,
-                "quantity": request.quantity,
-                "tx_hash": tx_hash,
-                "status": "Confirmed",
-                "created_at": chrono::Utc::now().to_rfc3339()
-            });
-
-            Ok(Json(json!({
-                "success": true,
-                "message": "Compra realizada com sucesso",
-                "data": transaction
-            })))
-        },
-        Err(e) => {
-            tracing::error!("Erro na compra: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    user: AuthUser,
+    payload: Result<Json<PurchaseRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let tx_hash: H256 = request
+        .tx_hash
+        .parse()
+        .map_err(|_| ApiError::Validation("tx_hash deve ser um hash 0x de 32 bytes".to_string()))?;
+    let tx_hash_text = format!("{:?}", tx_hash);
+
+    let transactions = TransactionRepository::new(state.db.clone());
+    if let Some(existing) = transactions.find_by_tx_hash(&tx_hash_text).await? {
+        if !existing.buyer.eq_ignore_ascii_case(&user.address) || existing.listing_id != request.listing_id {
+            return Err(ApiError::Conflict("Essa transação já foi registrada".to_string()));
         }
+        return Ok(ApiResponse::success_with_message(
+            with_ether(json!(existing), &["price"]),
+            "Compra já registrada",
+        ));
+    }
+
+    let (listing, service, nft_contract, token_id) =
+        purchasable_listing(&state, request.listing_id, &user).await?;
+    tracing::info!("💰 Registrando compra da oferta {} por {}", listing.id, user.address);
+
+    let sent = service
+        .get_transaction(tx_hash)
+        .await
+        .map_err(blockchain_error("consultar a compra"))?
+        .ok_or_else(|| ApiError::Validation("Transação não encontrada na rede".to_string()))?;
+    if sent.from != session_wallet(&user)? {
+        return Err(ApiError::Forbidden("a transação foi enviada por outra carteira".to_string()));
+    }
+    let paid = service
+        .purchase_payment(&sent, nft_contract, token_id)
+        .map_err(blockchain_error("conferir a compra"))?
+        .ok_or_else(|| ApiError::Validation("A transação não compra esta oferta".to_string()))?;
+    if paid < listing.price.into() {
+        return Err(ApiError::Validation(format!(
+            "Pagamento de {} ETH abaixo do preço da oferta",
+            units::format_ether(paid)
+        )));
     }
+
+    let transaction = Transaction {
+        id: Uuid::new_v4(),
+        listing_id: listing.id,
+        buyer: user.address,
+        seller: listing.seller.clone(),
+        price: listing.price,
+        quantity: listing.quantity,
+        tx_hash: Some(tx_hash_text),
+        status: TransactionStatus::Pending,
+        created_at: crate::db::now(),
+        completed_at: None,
+    };
+    transactions.create(&transaction).await?;
+
+    Ok(ApiResponse::success_with_message(
+        with_ether(json!(transaction), &["price"]),
+        "Compra registrada, aguardando confirmação",
+    ))
 }
 
-async fn list_transactions(State(_state): State<AppState>) -> Json<Value> {
-    let transactions = vec![
-        json!({
-            "id": Uuid::new_v4(),
-            "listing_id": Uuid::new_v4(),
-            "buyer": "0x1111111111111111111111111111111111111111",
-            "seller": "0x2222222222222222222222222222222222222222",
-            "price": "1000000000000000000",
-            "quantity": 10,
-            "tx_hash": "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890",
-            "status": "Confirmed",
-            "created_at": chrono::Utc::now().to_rfc3339()
-        })
-    ];
+/// Oferta ativa, dentro da validade e de outro vendedor, com o token on-chain
+/// e a rede em que ele está.
+async fn purchasable_listing(
+    state: &AppState,
+    listing_id: Uuid,
+    user: &AuthUser,
+) -> Result<(Listing, Arc<BlockchainService>, Address, U256), ApiError> {
+    let listing = ListingRepository::new(state.db.clone())
+        .find_by_id(listing_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
-    Json(json!({
+    if !matches!(listing.status, ListingStatus::Active) {
+        return Err(ApiError::Validation("Oferta não está ativa".to_string()));
+    }
+    if listing.expires_at.is_some_and(|expires_at| expires_at <= chrono::Utc::now()) {
+        return Err(ApiError::Validation("Oferta expirada".to_string()));
+    }
+    if listing.seller.eq_ignore_ascii_case(&user.address) {
+        return Err(ApiError::Validation("O vendedor não pode comprar a própria oferta".to_string()));
+    }
+
+    let asset = AssetRepository::new(state.db.clone())
+        .find_by_id(listing.asset_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let (service, nft_contract, token_id) = onchain_token(state, &asset)?;
+
+    Ok((listing, service, nft_contract, token_id))
+}
+
+async fn list_transactions(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let transactions = TransactionRepository::new(state.db.clone()).list().await?;
+
+    Ok(Json(json!({
         "success": true,
-        "data": transactions,
+        "data": with_ether(json!(transactions), &["price"]),
         "count": transactions.len()
-    }))
+    })))
+}
+
+/// Aceita o id interno (UUID) ou o hash da transação on-chain.
+async fn get_transaction(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let transactions = TransactionRepository::new(state.db.clone());
+
+    let transaction = match Uuid::parse_str(&reference) {
+        Ok(id) => transactions.find_by_id(id).await?,
+        Err(_) => {
+            // Gravado como em `purchase_tokens`: minúsculas, com `0x`.
+            let tx_hash: H256 = reference.parse().map_err(|_| {
+                ApiError::Validation("Informe o id da transação ou um hash 0x de 32 bytes".to_string())
+            })?;
+            transactions.find_by_tx_hash(&format!("{:?}", tx_hash)).await?
+        }
+    }
+    .ok_or(ApiError::NotFound)?;
+
+    // Profundidade atual, quando o nó responde; o status gravado continua
+    // sendo a fonte de verdade.
+    let mut confirmations = None;
+    let service = transaction_network(&state, &transaction).await?;
+    if let (Some(service), Some(tx_hash)) = (
+        service,
+        transaction.tx_hash.as_deref().and_then(|h| h.parse::<H256>().ok()),
+    ) {
+        let receipt = service.get_transaction_receipt(tx_hash).await.ok().flatten();
+        let head = service.get_block_number().await.ok();
+        if let (Some(receipt), Some(head)) = (receipt, head) {
+            confirmations = Some(crate::services::tx_tracker::confirmations(&receipt, head.as_u64()));
+        }
+    }
+
+    Ok(ApiResponse::success(json!({
+        "transaction": with_ether(json!(transaction), &["price"]),
+        "confirmations": confirmations,
+        "required_confirmations": state.config.blockchain.confirmations
+    })))
+}
+
+/// Cada rede tem o próprio Marketplace; `?chain=` escolhe qual (padrão:
+/// a rede padrão).
+async fn get_fees(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let service = fee_network(&state, &query)?;
+    let fees = service.marketplace_fees().await.map_err(|e| {
+        tracing::error!("Erro ao consultar taxas do marketplace: {}", e);
+        ApiError::Blockchain(e.to_string())
+    })?;
+
+    Ok(ApiResponse::success(json!({
+        "chain_id": service.chain_id(),
+        "fee_bps": fees.fee_bps.to_string(),
+        "accrued_wei": fees.accrued.to_string(),
+        "accrued_eth": units::format_ether(fees.accrued)
+    })))
+}
+
+async fn update_fee(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    Query(query): Query<ChainQuery>,
+    payload: Result<Json<UpdateFeeRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let service = fee_network(&state, &query)?;
+    if request.fee_bps > MAX_FEE_BPS {
+        return Err(ApiError::Validation(format!(
+            "Taxa máxima é de {} pontos-base (10%)",
+            MAX_FEE_BPS
+        )));
+    }
+
+    tracing::info!(
+        "💸 {} alterando a taxa do marketplace em {} para {} bps",
+        admin.address,
+        service.network().name,
+        request.fee_bps
+    );
+
+    let tx_hash = service
+        .update_marketplace_fee(U256::from(request.fee_bps))
+        .await
+        .map_err(|e| {
+            tracing::error!("Erro ao alterar taxa do marketplace: {}", e);
+            ApiError::blockchain(e)
+        })?;
+
+    Ok(ApiResponse::success_with_message(
+        json!({
+            "chain_id": service.chain_id(),
+            "fee_bps": request.fee_bps,
+            "tx_hash": format!("{:?}", tx_hash)
+        }),
+        "Taxa do marketplace atualizada",
+    ))
+}
+
+async fn withdraw_fees(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let service = fee_network(&state, &query)?;
+    tracing::info!("💸 {} sacando as taxas do marketplace em {}", admin.address, service.network().name);
+
+    let tx_hash = service.withdraw_fees().await.map_err(|e| {
+        tracing::error!("Erro ao sacar taxas do marketplace: {}", e);
+        ApiError::blockchain(e)
+    })?;
+
+    Ok(ApiResponse::success_with_message(
+        json!({ "chain_id": service.chain_id(), "tx_hash": format!("{:?}", tx_hash) }),
+        "Saque das taxas enviado",
+    ))
+}
+
+/// Eventos do Marketplace emitidos por uma transação enviada pela carteira
+/// do usuário, que precisa estar minerada e ter tido sucesso. Devolve também
+/// o hash normalizado.
+/// Transação do Marketplace minerada com sucesso: hash normalizado, bloco e
+/// os eventos com o índice do log de cada um.
+struct MinedMarketplaceTx {
+    tx_hash: String,
+    block_number: u64,
+    events: Vec<(u64, MarketplaceEvents)>,
+}
+
+async fn marketplace_events(service: &BlockchainService, tx_hash: &str) -> Result<MinedMarketplaceTx, ApiError> {
+    let tx_hash: H256 = tx_hash
+        .parse()
+        .map_err(|_| ApiError::Validation("tx_hash deve ser um hash 0x de 32 bytes".to_string()))?;
+
+    let (receipt, events) = service
+        .marketplace_receipt(tx_hash)
+        .await
+        .map_err(blockchain_error("consultar a transação"))?
+        .ok_or_else(|| {
+            ApiError::Conflict("A transação ainda não foi minerada; tente novamente em instantes".to_string())
+        })?;
+    if receipt.status != Some(1.into()) {
+        return Err(ApiError::Validation("A transação foi revertida".to_string()));
+    }
+    let block_number = receipt.block_number.ok_or_else(|| {
+        ApiError::Conflict("A transação ainda não foi minerada; tente novamente em instantes".to_string())
+    })?;
+
+    Ok(MinedMarketplaceTx {
+        tx_hash: format!("{:?}", tx_hash),
+        block_number: block_number.as_u64(),
+        events,
+    })
+}
+
+fn session_wallet(user: &AuthUser) -> Result<Address, ApiError> {
+    user.address
+        .parse()
+        .map_err(|_| ApiError::Validation("Endereço da sessão inválido".to_string()))
+}
+
+fn blockchain_error(action: &'static str) -> impl Fn(Box<dyn std::error::Error>) -> ApiError {
+    move |e| {
+        tracing::error!("Erro ao {}: {}", action, e);
+        ApiError::blockchain(e)
+    }
+}
+
+/// Preço em wei: `price` ou `price_eth` em ETH decimal.
+fn listing_price(request: &PrepareListingRequest) -> Result<Wei, ApiError> {
+    match (request.price, request.price_eth.as_deref()) {
+        (Some(price), None) => Ok(price),
+        (None, Some(price_eth)) => Ok(Wei(units::parse_ether(price_eth)?)),
+        (Some(_), Some(_)) => Err(ApiError::Validation("Informe price ou price_eth, não ambos".to_string())),
+        (None, None) => Err(ApiError::Validation("Preço é obrigatório (price ou price_eth)".to_string())),
+    }
+}
+
+fn fee_network(state: &AppState, query: &ChainQuery) -> Result<Arc<BlockchainService>, ApiError> {
+    network(state, query).ok_or_else(|| ApiError::Validation("Rede desconhecida".to_string()))
+}
+
+/// Rede da oferta comprada; `None` se o ativo não existe mais ou a rede
+/// saiu da configuração.
+async fn transaction_network(
+    state: &AppState,
+    transaction: &Transaction,
+) -> Result<Option<Arc<BlockchainService>>, ApiError> {
+    let Some(listing) = ListingRepository::new(state.db.clone())
+        .find_by_id(transaction.listing_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let chain_id = AssetRepository::new(state.db.clone())
+        .find_by_id(listing.asset_id)
+        .await?
+        .and_then(|asset| asset.chain_id);
+
+    Ok(chain_id.and_then(|chain_id| state.networks.get(chain_id)))
+}
+
+/// Contrato e token do ativo, com o serviço da rede em que foi emitido.
+fn onchain_token(state: &AppState, asset: &Asset) -> Result<(Arc<BlockchainService>, Address, U256), ApiError> {
+    let nft_contract = asset.token_address.as_deref().and_then(|a| a.parse().ok());
+    let token_id = asset
+        .token_id
+        .as_deref()
+        .and_then(|id| U256::from_dec_str(id).ok());
+
+    match (nft_contract, token_id) {
+        (Some(nft_contract), Some(token_id)) => Ok((asset_network(state, asset)?, nft_contract, token_id)),
+        _ => Err(ApiError::Validation("Ativo ainda não foi tokenizado".to_string())),
+    }
 }
@@ -3,17 +3,24 @@ use axum::{
     Router,
 };
 
+mod api_keys;
 mod assets;
+mod auth;
 mod marketplace;
+mod roles;
 mod waitlist;
 mod blockchain;
+mod documents;
 
 use crate::AppState;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/docs", get(api_docs))
-        .nest("/assets", assets::routes())
+        .nest("/auth", auth::routes())
+        .nest("/roles", roles::routes())
+        .nest("/api-keys", api_keys::routes())
+        .nest("/assets", assets::routes().merge(documents::routes()))
         .nest("/marketplace", marketplace::routes())
         .nest("/waitlist", waitlist::routes())
         .nest("/blockchain", blockchain::routes())
@@ -25,23 +32,66 @@ async fn api_docs() -> &'static str {
 
 ## Endpoints disponíveis:
 
+### Auth (Sign-In with Ethereum)
+- GET /api/v1/auth/nonce - Nonce para a mensagem EIP-4361
+- POST /api/v1/auth/login - Login com mensagem e assinatura; devolve o token de sessão
+- GET /api/v1/auth/session - Sessão atual (Authorization: Bearer <token>)
+- POST /api/v1/auth/logout - Encerrar a sessão atual
+
+### Papéis (admin)
+- GET /api/v1/roles - Listar papéis concedidos
+- GET /api/v1/roles/{address} - Papéis de um endereço
+- POST /api/v1/roles - Conceder papel (Admin, Appraiser, Issuer, Investor)
+- DELETE /api/v1/roles/{address}/{role} - Remover papel
+
+### Chaves de API (admin)
+- GET /api/v1/api-keys - Listar chaves
+- POST /api/v1/api-keys - Criar chave (a chave só é exibida na criação)
+- DELETE /api/v1/api-keys/{id} - Revogar chave
+
 ### Assets
-- GET /api/v1/assets - Listar ativos
-- POST /api/v1/assets - Criar ativo
+- GET /api/v1/assets - Buscar ativos (asset_type, status, owner, min_value, max_value, location, created_from, created_to, q, sort, order, limit, cursor)
+- POST /api/v1/assets - Criar ativo (autenticado; proprietário = carteira da sessão)
 - GET /api/v1/assets/{id} - Obter ativo
-- POST /api/v1/assets/{id}/tokenize - Tokenizar ativo
+- PATCH /api/v1/assets/{id} - Editar ativo em Draft/PendingApproval (proprietário; exige updated_at)
+- DELETE /api/v1/assets/{id}?updated_at= - Retirar ou excluir ativo (proprietário; exige updated_at)
+- POST /api/v1/assets/{id}/submit - Enviar para aprovação (proprietário)
+- POST /api/v1/assets/{id}/approve - Aprovar ativo (appraiser)
+- POST /api/v1/assets/{id}/reject - Rejeitar ativo com motivo (appraiser)
+- POST /api/v1/assets/{id}/retire - Retirar ativo (proprietário ou admin)
+- GET /api/v1/assets/{id}/history - Histórico de status
+- POST /api/v1/assets/{id}/tokenize - Tokenizar ativo aprovado na rede do campo chain (padrão: DEFAULT_NETWORK); o NFT vai para o proprietário (issuer)
+- POST /api/v1/assets/{id}/verify - Verificar ativo on-chain (appraiser)
+- GET /api/v1/assets/{id}/documents - Listar documentos (sem sessão: só imagens de ativos aprovados)
+- POST /api/v1/assets/{id}/documents - Enviar documento (multipart: kind, file; proprietário)
+- GET /api/v1/assets/{id}/documents/{document_id} - Baixar documento (proprietário, Appraiser ou Admin; imagens públicas)
+- DELETE /api/v1/assets/{id}/documents/{document_id} - Remover documento (proprietário)
 
 ### Marketplace
 - GET /api/v1/marketplace/listings - Listar ofertas
-- POST /api/v1/marketplace/listings - Criar oferta
-- POST /api/v1/marketplace/purchase - Comprar tokens
+- POST /api/v1/marketplace/listings/prepare - Transações approve/listAsset para a carteira do vendedor assinar
+- POST /api/v1/marketplace/listings - Registrar oferta a partir do tx_hash do listAsset (vendedor = carteira da sessão)
+- GET /api/v1/marketplace/listings/{id} - Obter oferta
+- POST /api/v1/marketplace/listings/{id}/cancel/prepare - Transação cancelListing para o vendedor assinar
+- POST /api/v1/marketplace/listings/{id}/cancel - Registrar cancelamento a partir do tx_hash (vendedor ou admin)
+- POST /api/v1/marketplace/purchase/prepare - Transação buyAsset para a carteira do comprador assinar e pagar (autenticado)
+- POST /api/v1/marketplace/purchase - Registrar compra a partir do tx_hash (comprador = carteira da sessão)
+- GET /api/v1/marketplace/transactions - Listar transações
+- GET /api/v1/marketplace/transactions/{id|tx_hash} - Status de uma transação
+- GET /api/v1/marketplace/fees?chain= - Taxa e saldo acumulado
+- PUT /api/v1/marketplace/fees?chain= - Alterar taxa (admin)
+- POST /api/v1/marketplace/fees/withdraw?chain= - Sacar taxas (admin)
 
 ### Waitlist
 - POST /api/v1/waitlist - Adicionar à lista de espera
 - GET /api/v1/waitlist - Listar entradas (admin)
 
 ### Blockchain
-- GET /api/v1/blockchain/status - Status da blockchain
+- GET /api/v1/blockchain/status?chain= - Status de cada rede (ou de uma só)
 - GET /api/v1/blockchain/balance/{address} - Saldo de um endereço
+- GET /api/v1/blockchain/balance/{address}/tokens?chain= - Ativos da plataforma do endereço
+- GET /api/v1/blockchain/operations - Operações da carteira da plataforma
+- GET /api/v1/blockchain/operations/{id} - Operação e tentativas de envio
+- POST /api/v1/blockchain/nonces/{nonce}/cancel?chain= - Cancelar um nonce pendente (admin)
 "#
 }
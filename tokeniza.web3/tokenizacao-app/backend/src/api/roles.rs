@@ -0,0 +1,102 @@
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    auth::{roles, RequireRole},
+    db::RoleRepository,
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{GrantRoleRequest, Role, RoleGrant},
+    AppState,
+};
+
+/// Gestão de papéis; todas as rotas exigem `Admin`.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_roles).post(grant_role))
+        .route("/:address", get(get_roles))
+        .route("/:address/:role", delete(revoke_role))
+}
+
+async fn list_roles(
+    State(state): State<AppState>,
+    _admin: RequireRole<roles::Admin>,
+) -> Result<Json<Value>, ApiError> {
+    let grants = RoleRepository::new(state.db.clone()).list().await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": grants,
+        "count": grants.len()
+    })))
+}
+
+async fn get_roles(
+    State(state): State<AppState>,
+    _admin: RequireRole<roles::Admin>,
+    Path(address): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let grants = RoleRepository::new(state.db.clone())
+        .grants_of(&address)
+        .await?;
+
+    Ok(ApiResponse::success(json!({
+        "address": address.to_lowercase(),
+        "roles": grants
+    })))
+}
+
+async fn grant_role(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    payload: Result<Json<GrantRoleRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    if !crate::services::blockchain::is_valid_address(&request.address) {
+        return Err(ApiError::Validation("Endereço inválido".to_string()));
+    }
+
+    let grant = RoleGrant {
+        address: request.address.to_lowercase(),
+        role: request.role,
+        granted_by: Some(admin.address.clone()),
+        created_at: crate::db::now(),
+    };
+    let created = RoleRepository::new(state.db.clone()).grant(&grant).await?;
+
+    if created {
+        tracing::info!("🛡️ {} concedeu o papel {:?} a {}", admin.address, grant.role, grant.address);
+    }
+
+    Ok(ApiResponse::success_with_message(
+        json!(grant),
+        if created { "Papel concedido" } else { "Endereço já possuía o papel" },
+    ))
+}
+
+async fn revoke_role(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<roles::Admin>,
+    Path((address, role)): Path<(String, Role)>,
+) -> Result<Json<Value>, ApiError> {
+    let address = address.to_lowercase();
+    // Evita que o último acesso administrativo se perca por engano.
+    if role == Role::Admin && address == admin.address {
+        return Err(ApiError::Validation("Não é possível remover o próprio papel de Admin".to_string()));
+    }
+
+    if !RoleRepository::new(state.db.clone()).revoke(&address, role).await? {
+        return Err(ApiError::NotFound);
+    }
+
+    tracing::info!("🛡️ {} removeu o papel {:?} de {}", admin.address, role, address);
+
+    Ok(ApiResponse::success_with_message(
+        json!({ "address": address, "role": role }),
+        "Papel removido",
+    ))
+}
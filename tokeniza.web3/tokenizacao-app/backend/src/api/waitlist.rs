@@ -1,6 +1,5 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{rejection::JsonRejection, State},
     response::Json,
     routing::{get, post},
     Router,
@@ -9,7 +8,10 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::{
-    models::AddToWaitlistRequest,
+    auth::{roles, RequireRole},
+    db::WaitlistRepository,
+    handlers::{error::ApiError, response::ApiResponse},
+    models::{AddToWaitlistRequest, WaitlistEntry, WaitlistStatus},
     AppState,
 };
 
@@ -21,95 +23,60 @@ pub fn routes() -> Router<AppState> {
 }
 
 async fn add_to_waitlist(
-    State(_state): State<AppState>,
-    Json(request): Json<AddToWaitlistRequest>,
-) -> Result<Json<Value>, StatusCode> {
+    State(state): State<AppState>,
+    payload: Result<Json<AddToWaitlistRequest>, JsonRejection>,
+) -> Result<Json<Value>, ApiError> {
+    let Json(request) = payload?;
+    let email = request.email.trim().to_lowercase();
+
     // Validar email
-    if !is_valid_email(&request.email) {
-        return Err(StatusCode::BAD_REQUEST);
+    if !is_valid_email(&email) {
+        return Err(ApiError::Validation("Email inválido".to_string()));
     }
 
-    let entry_id = Uuid::new_v4();
-    
-    tracing::info!("📧 Adicionando {} à waitlist", request.email);
+    tracing::info!("📧 Adicionando {} à waitlist", email);
 
-    // Em produção, salvar no banco de dados
-    let waitlist_entry = json!({
-        "id": entry_id,
-        "email": request.email,
-        "name": request.name,
-        "interest_areas": request.interest_areas,
-        "investment_range": request.investment_range,
-        "status": "Pending",
-        "created_at": chrono::Utc::now().to_rfc3339()
-    });
+    let entry = WaitlistEntry {
+        id: Uuid::new_v4(),
+        email,
+        name: request.name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty()),
+        interest_areas: request.interest_areas,
+        investment_range: request.investment_range,
+        status: WaitlistStatus::Pending,
+        created_at: crate::db::now(),
+        contacted_at: None,
+    };
 
-    Ok(Json(json!({
-        "success": true,
-        "message": "Adicionado à lista de espera com sucesso!",
-        "data": waitlist_entry
-    })))
+    if !WaitlistRepository::new(state.db.clone()).create(&entry).await? {
+        return Err(ApiError::Conflict("Email já está na lista de espera".to_string()));
+    }
+
+    Ok(ApiResponse::success_with_message(
+        json!(entry),
+        "Adicionado à lista de espera com sucesso!",
+    ))
 }
 
-async fn list_waitlist(State(_state): State<AppState>) -> Json<Value> {
-    // Em produção, isso seria protegido por autenticação admin
-    let entries = vec![
-        json!({
-            "id": Uuid::new_v4(),
-            "email": "user1@example.com",
-            "name": "João Silva",
-            "interest_areas": ["RealEstate", "Art"],
-            "investment_range": "Range50K100K",
-            "status": "Pending",
-            "created_at": chrono::Utc::now().to_rfc3339()
-        }),
-        json!({
-            "id": Uuid::new_v4(),
-            "email": "user2@example.com",
-            "name": "Maria Santos",
-            "interest_areas": ["Commodity"],
-            "investment_range": "Range100K500K",
-            "status": "Contacted",
-            "created_at": chrono::Utc::now().to_rfc3339()
-        })
-    ];
+async fn list_waitlist(
+    State(state): State<AppState>,
+    _admin: RequireRole<roles::Admin>,
+) -> Result<Json<Value>, ApiError> {
+    let entries = WaitlistRepository::new(state.db.clone()).list().await?;
 
-    Json(json!({
+    Ok(Json(json!({
         "success": true,
         "data": entries,
         "count": entries.len()
-    }))
+    })))
 }
 
-async fn waitlist_stats(State(_state): State<AppState>) -> Json<Value> {
-    // Em produção, calcular estatísticas reais do banco de dados
-    let stats = json!({
-        "total_entries": 1247,
-        "pending": 892,
-        "contacted": 245,
-        "converted": 89,
-        "unsubscribed": 21,
-        "by_interest": {
-            "RealEstate": 456,
-            "Art": 234,
-            "Commodity": 187,
-            "Stock": 156,
-            "Bond": 89,
-            "Other": 125
-        },
-        "by_investment_range": {
-            "Under10K": 234,
-            "Range10K50K": 345,
-            "Range50K100K": 267,
-            "Range100K500K": 234,
-            "Over500K": 167
-        }
-    });
+async fn waitlist_stats(
+    State(state): State<AppState>,
+    _admin: RequireRole<roles::Admin>,
+) -> Result<Json<Value>, ApiError> {
+    let stats = WaitlistRepository::new(state.db.clone()).stats().await?;
 
-    Json(json!({
-        "success": true,
-        "data": stats
-    }))
+    Ok(ApiResponse::success(json!(stats)))
 }
 
 fn is_valid_email(email: &str) -> bool {
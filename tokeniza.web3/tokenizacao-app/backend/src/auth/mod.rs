@@ -0,0 +1,219 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, HeaderName},
+};
+use chrono::{DateTime, Utc};
+use ethers::core::rand::{thread_rng, RngCore};
+use ethers::utils::{hex, keccak256};
+use serde::Serialize;
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+use crate::{
+    db::{ApiKeyRepository, RoleRepository, SessionRepository},
+    handlers::error::ApiError,
+    models::{Role, RoleGrant},
+    AppState,
+};
+
+pub mod rate_limit;
+pub mod siwe;
+
+/// Cabeçalho com a chave de API, alternativa à sessão de carteira.
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Quem fez a requisição: uma carteira com sessão SIWE
+/// (`Authorization: Bearer <token>`) ou uma integração com chave de API
+/// (`X-API-Key`). Handlers que o recebem como argumento respondem 401 a
+/// requisições anônimas.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthUser {
+    /// Endereço em minúsculas, no mesmo formato gravado em `owner`/`seller`.
+    pub address: String,
+    /// Papéis da carteira ou, para chaves de API, os escopos da chave.
+    pub roles: Vec<Role>,
+    pub credential: Credential,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Credential {
+    Session { id: Uuid, expires_at: DateTime<Utc> },
+    ApiKey { id: Uuid, name: String },
+}
+
+impl AuthUser {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&Role::Admin) || self.roles.contains(&role)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        if let Some(key) = parts.headers.get(API_KEY_HEADER) {
+            let key = key.to_str().map_err(|_| ApiError::Unauthorized)?.trim();
+            return authenticate_api_key(state, key).await;
+        }
+
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or(ApiError::Unauthorized)?;
+
+        let session = SessionRepository::new(state.db.clone())
+            .find_active(&hash_token(token))
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+
+        let roles = RoleRepository::new(state.db.clone())
+            .roles_of(&session.address)
+            .await?;
+
+        Ok(AuthUser {
+            address: session.address,
+            roles,
+            credential: Credential::Session {
+                id: session.id,
+                expires_at: session.expires_at,
+            },
+        })
+    }
+}
+
+/// Para rotas abertas que mostram mais a quem se identifica: `None` quando a
+/// requisição não traz credencial nenhuma. Uma credencial inválida ou
+/// expirada continua respondendo 401, em vez de virar acesso anônimo.
+pub struct MaybeAuthUser(pub Option<AuthUser>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for MaybeAuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !parts.headers.contains_key(API_KEY_HEADER) && !parts.headers.contains_key(AUTHORIZATION) {
+            return Ok(MaybeAuthUser(None));
+        }
+
+        AuthUser::from_request_parts(parts, state).await.map(|user| MaybeAuthUser(Some(user)))
+    }
+}
+
+async fn authenticate_api_key(state: &AppState, key: &str) -> Result<AuthUser, ApiError> {
+    let keys = ApiKeyRepository::new(state.db.clone());
+    let api_key = keys
+        .find_active(&hash_token(key))
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+
+    if let Err(retry_after) = state.api_key_limiter.check(api_key.id, api_key.rate_limit_per_minute) {
+        tracing::warn!("🚦 Chave {} ({}) acima do limite", api_key.prefix, api_key.name);
+        return Err(ApiError::TooManyRequests(format!(
+            "{} requisições por minuto; tente novamente em {} s",
+            api_key.rate_limit_per_minute, retry_after
+        )));
+    }
+
+    keys.touch(api_key.id, &crate::db::now()).await?;
+
+    Ok(AuthUser {
+        address: api_key.address,
+        roles: api_key.scopes,
+        credential: Credential::ApiKey {
+            id: api_key.id,
+            name: api_key.name,
+        },
+    })
+}
+
+/// Papel exigido por um `RequireRole`.
+pub trait RequiredRole {
+    const ROLE: Role;
+}
+
+/// Marcadores para `RequireRole<roles::Admin>` e afins.
+pub mod roles {
+    use super::RequiredRole;
+    use crate::models::Role;
+
+    pub struct Admin;
+    pub struct Appraiser;
+    pub struct Issuer;
+
+    impl RequiredRole for Admin {
+        const ROLE: Role = Role::Admin;
+    }
+
+    impl RequiredRole for Appraiser {
+        const ROLE: Role = Role::Appraiser;
+    }
+
+    impl RequiredRole for Issuer {
+        const ROLE: Role = Role::Issuer;
+    }
+}
+
+/// Como `AuthUser`, mas responde 403 se a carteira não tiver o papel `R`
+/// (ou `Admin`).
+pub struct RequireRole<R: RequiredRole>(pub AuthUser, pub PhantomData<R>);
+
+#[async_trait]
+impl<R: RequiredRole> FromRequestParts<AppState> for RequireRole<R> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        if !user.has_role(R::ROLE) {
+            tracing::warn!("🔒 {} sem o papel {:?} exigido", user.address, R::ROLE);
+            return Err(ApiError::Forbidden(format!("requer o papel {:?}", R::ROLE)));
+        }
+
+        Ok(RequireRole(user, PhantomData))
+    }
+}
+
+/// Garante o papel `Admin` às carteiras de `ADMIN_ADDRESSES`; é assim que o
+/// primeiro admin existe para conceder os demais papéis.
+pub async fn grant_configured_admins(
+    db: &crate::db::DbPool,
+    addresses: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let roles = RoleRepository::new(db.clone());
+    for address in addresses {
+        if !crate::services::blockchain::is_valid_address(address) {
+            return Err(format!("ADMIN_ADDRESSES contém um endereço inválido: {}", address).into());
+        }
+        let grant = RoleGrant {
+            address: address.clone(),
+            role: Role::Admin,
+            granted_by: None,
+            created_at: crate::db::now(),
+        };
+        if roles.grant(&grant).await? {
+            tracing::info!("🛡️ Papel Admin concedido a {}", address);
+        }
+    }
+
+    Ok(())
+}
+
+/// Valor aleatório em hexadecimal, usado para nonces SIWE, tokens de sessão
+/// e chaves de API.
+pub fn random_token(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    thread_rng().fill_bytes(&mut buffer);
+    hex::encode(buffer)
+}
+
+/// Os tokens são gravados apenas como hash; com 256 bits de entropia, um
+/// hash rápido basta.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(keccak256(token.as_bytes()))
+}
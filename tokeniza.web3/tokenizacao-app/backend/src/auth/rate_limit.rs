@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Limite por chave de API em balde de fichas: cada chave acumula até
+/// `per_minute` fichas, repostas continuamente ao longo do minuto, e cada
+/// requisição gasta uma. Rajadas curtas passam; uso sustentado acima do
+/// limite, não. O estado é por processo.
+#[derive(Default)]
+pub struct KeyRateLimiter {
+    buckets: Mutex<HashMap<Uuid, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl KeyRateLimiter {
+    /// `Err` traz quantos segundos faltam para a próxima ficha.
+    pub fn check(&self, key: Uuid, per_minute: u32) -> Result<(), u64> {
+        let capacity = f64::from(per_minute.max(1));
+        let per_second = capacity / 60.0;
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / per_second).ceil() as u64)
+        }
+    }
+}
@@ -0,0 +1,366 @@
+// Mensagens Sign-In with Ethereum (EIP-4361): leitura do texto assinado pela
+// carteira e verificação da assinatura (EIP-191) contra o endereço declarado.
+
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, Signature};
+use ethers::utils::to_checksum;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::handlers::error::ApiError;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// Tolerância para relógios adiantados na carteira ao conferir `Issued At`.
+const CLOCK_SKEW_SECS: i64 = 300;
+
+#[derive(Error, Debug)]
+pub enum SiweError {
+    #[error("mensagem SIWE malformada: {0}")]
+    Malformed(String),
+
+    #[error("assinatura inválida: {0}")]
+    Signature(String),
+
+    #[error("domínio '{found}' não corresponde a '{expected}'")]
+    DomainMismatch { expected: String, found: String },
+
+    #[error("Chain ID {0} não é uma rede configurada")]
+    UnsupportedChain(u64),
+
+    #[error("mensagem expirada")]
+    Expired,
+
+    #[error("mensagem ainda não é válida")]
+    NotYetValid,
+}
+
+impl From<SiweError> for ApiError {
+    fn from(err: SiweError) -> Self {
+        match err {
+            SiweError::Malformed(_) | SiweError::UnsupportedChain(_) => {
+                ApiError::Validation(err.to_string())
+            }
+            _ => {
+                tracing::warn!("🔒 Login SIWE recusado: {}", err);
+                ApiError::Unauthorized
+            }
+        }
+    }
+}
+
+/// Campos da mensagem de que o servidor precisa; declaração, URI e recursos
+/// são conferidos na leitura mas servem apenas ao usuário que assina.
+#[derive(Debug, Clone)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: Address,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let malformed = |detail: &str| SiweError::Malformed(detail.to_string());
+        let mut lines = message.lines().peekable();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER_SUFFIX))
+            .filter(|domain| !domain.is_empty())
+            .ok_or_else(|| malformed("cabeçalho \"<domínio> wants you to sign in...\" ausente"))?
+            .to_string();
+
+        // O endereço precisa vir em EIP-55, como as carteiras o exibem.
+        let raw_address = lines.next().ok_or_else(|| malformed("endereço ausente"))?;
+        let address: Address = raw_address
+            .parse()
+            .map_err(|_| malformed("endereço inválido"))?;
+        if to_checksum(&address, None) != raw_address {
+            return Err(malformed("endereço fora do formato EIP-55"));
+        }
+
+        if lines.next() != Some("") {
+            return Err(malformed("linha em branco esperada após o endereço"));
+        }
+        // A declaração é opcional; sem ela, algumas carteiras deixam uma
+        // segunda linha em branco.
+        match lines.peek() {
+            Some(&"") => {
+                lines.next();
+            }
+            Some(line) if !line.starts_with("URI: ") => {
+                lines.next();
+                if lines.next() != Some("") {
+                    return Err(malformed("linha em branco esperada após a declaração"));
+                }
+            }
+            _ => {}
+        }
+
+        let mut fields = std::collections::HashMap::new();
+        while let Some(line) = lines.next() {
+            if line == "Resources:" {
+                if !lines.by_ref().all(|resource| resource.starts_with("- ")) {
+                    return Err(malformed("recurso deve começar com \"- \""));
+                }
+                break;
+            }
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| SiweError::Malformed(format!("linha inesperada '{}'", line)))?;
+            if fields.insert(key, value).is_some() {
+                return Err(SiweError::Malformed(format!("campo '{}' repetido", key)));
+            }
+        }
+
+        let required = |key: &str| {
+            fields
+                .get(key)
+                .map(|value| value.to_string())
+                .ok_or_else(|| SiweError::Malformed(format!("campo '{}' ausente", key)))
+        };
+        let timestamp = |key: &str| -> Result<Option<DateTime<Utc>>, SiweError> {
+            fields
+                .get(key)
+                .map(|value| {
+                    DateTime::parse_from_rfc3339(value)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .map_err(|_| SiweError::Malformed(format!("'{}' não é uma data RFC 3339", key)))
+                })
+                .transpose()
+        };
+
+        if required("URI")?.is_empty() {
+            return Err(malformed("URI vazia"));
+        }
+        if required("Version")? != "1" {
+            return Err(malformed("apenas a versão 1 é suportada"));
+        }
+        let nonce = required("Nonce")?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(malformed("nonce deve ter ao menos 8 caracteres alfanuméricos"));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            chain_id: required("Chain ID")?
+                .parse()
+                .map_err(|_| malformed("Chain ID inválido"))?,
+            nonce,
+            issued_at: timestamp("Issued At")?.ok_or_else(|| malformed("campo 'Issued At' ausente"))?,
+            expiration_time: timestamp("Expiration Time")?,
+            not_before: timestamp("Not Before")?,
+        })
+    }
+}
+
+impl SiweMessage {
+    /// Confere domínio, rede e janela de validade; o nonce fica a cargo de
+    /// quem chama, que tem acesso ao banco.
+    pub fn validate(
+        &self,
+        expected_domain: &str,
+        chain_ids: &[u64],
+        now: DateTime<Utc>,
+    ) -> Result<(), SiweError> {
+        let domain = self.domain.split_once("://").map_or(self.domain.as_str(), |(_, rest)| rest);
+        if !domain.eq_ignore_ascii_case(expected_domain) {
+            return Err(SiweError::DomainMismatch {
+                expected: expected_domain.to_string(),
+                found: self.domain.clone(),
+            });
+        }
+        if !chain_ids.contains(&self.chain_id) {
+            return Err(SiweError::UnsupportedChain(self.chain_id));
+        }
+        if self.expiration_time.is_some_and(|expiration| expiration <= now) {
+            return Err(SiweError::Expired);
+        }
+        if self.issued_at > now + chrono::Duration::seconds(CLOCK_SKEW_SECS)
+            || self.not_before.is_some_and(|not_before| not_before > now)
+        {
+            return Err(SiweError::NotYetValid);
+        }
+
+        Ok(())
+    }
+}
+
+/// Lê a mensagem e confirma que `signature` foi produzida pelo endereço que
+/// ela declara, sobre exatamente este texto.
+pub fn verify(message: &str, signature: &str) -> Result<SiweMessage, SiweError> {
+    let parsed: SiweMessage = message.parse()?;
+
+    let signature = Signature::from_str(signature.trim())
+        .map_err(|e| SiweError::Signature(e.to_string()))?;
+    signature
+        .verify(message, parsed.address)
+        .map_err(|e| SiweError::Signature(e.to_string()))?;
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::LocalWallet;
+    use ethers::utils::hash_message;
+
+    /// Exemplo da EIP-4361, com o endereço trocado pelo da chave abaixo para
+    /// que a assinatura possa ser conferida.
+    const EIP_MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0x2c7536E3605D9C16a7a3D7b1898e529396a65c23
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    const EIP_SIGNATURE: &str = "0xb27c4e33d60316e81c0ce54a4768316f80b73d2469a6da4b59dc7f6591eeae58\
+                                 64c59ee939dfcf4a87e0b481699b1792d175fe73af48424896fab83ac20a71e51c";
+
+    const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    fn sign(message: &str) -> String {
+        let wallet: LocalWallet = PRIVATE_KEY.parse().unwrap();
+        format!("0x{}", wallet.sign_hash(hash_message(message)).unwrap())
+    }
+
+    #[test]
+    fn verifies_the_eip_example() {
+        let message = verify(EIP_MESSAGE, EIP_SIGNATURE).unwrap();
+
+        assert_eq!(message.domain, "service.org");
+        assert_eq!(
+            to_checksum(&message.address, None),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_eq!(message.chain_id, 1);
+        assert_eq!(message.nonce, "32891756");
+        assert_eq!(message.issued_at, at("2021-09-30T16:25:24Z"));
+        assert!(message.expiration_time.is_none());
+        assert!(message.validate("service.org", &[1], at("2021-09-30T16:30:00Z")).is_ok());
+    }
+
+    #[test]
+    fn accepts_a_message_without_statement() {
+        let message = EIP_MESSAGE.replace(
+            "I accept the ServiceOrg Terms of Service: https://service.org/tos\n\n",
+            "",
+        );
+        assert!(verify(&message, &sign(&message)).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_bad_signature() {
+        // Assinatura válida, mas de outro texto.
+        let tampered = EIP_MESSAGE.replace("Nonce: 32891756", "Nonce: 32891757");
+        assert!(matches!(verify(&tampered, EIP_SIGNATURE), Err(SiweError::Signature(_))));
+
+        // Assinatura de outra carteira sobre o mesmo texto.
+        let other: LocalWallet = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            .parse()
+            .unwrap();
+        let foreign = format!("0x{}", other.sign_hash(hash_message(EIP_MESSAGE)).unwrap());
+        assert!(matches!(verify(EIP_MESSAGE, &foreign), Err(SiweError::Signature(_))));
+
+        assert!(matches!(verify(EIP_MESSAGE, "0x1234"), Err(SiweError::Signature(_))));
+    }
+
+    #[test]
+    fn rejects_the_wrong_domain() {
+        let message: SiweMessage = EIP_MESSAGE.parse().unwrap();
+        let now = at("2021-09-30T16:30:00Z");
+
+        assert!(matches!(
+            message.validate("evil.org", &[1], now),
+            Err(SiweError::DomainMismatch { .. })
+        ));
+        // Esquema e caixa não importam; subdomínios e portas, sim.
+        let with_scheme: SiweMessage =
+            EIP_MESSAGE.replacen("service.org", "https://Service.org", 1).parse().unwrap();
+        assert!(with_scheme.validate("service.org", &[1], now).is_ok());
+        assert!(message.validate("app.service.org", &[1], now).is_err());
+        assert!(message.validate("service.org:8080", &[1], now).is_err());
+    }
+
+    #[test]
+    fn rejects_an_unconfigured_chain() {
+        let message: SiweMessage = EIP_MESSAGE.parse().unwrap();
+        assert!(matches!(
+            message.validate("service.org", &[1337, 11155111], at("2021-09-30T16:30:00Z")),
+            Err(SiweError::UnsupportedChain(1))
+        ));
+    }
+
+    #[test]
+    fn enforces_the_validity_window() {
+        let message: SiweMessage = format!(
+            "{}\nExpiration Time: 2021-09-30T17:00:00Z\nNot Before: 2021-09-30T16:30:00Z",
+            EIP_MESSAGE.split("\nResources:").next().unwrap()
+        )
+        .parse()
+        .unwrap();
+
+        assert!(message.validate("service.org", &[1], at("2021-09-30T16:45:00Z")).is_ok());
+        assert!(matches!(
+            message.validate("service.org", &[1], at("2021-09-30T17:00:00Z")),
+            Err(SiweError::Expired)
+        ));
+        assert!(matches!(
+            message.validate("service.org", &[1], at("2021-09-30T16:29:59Z")),
+            Err(SiweError::NotYetValid)
+        ));
+
+        // Emitida no futuro além da tolerância de relógio.
+        let plain: SiweMessage = EIP_MESSAGE.parse().unwrap();
+        assert!(plain.validate("service.org", &[1], at("2021-09-30T16:21:00Z")).is_ok());
+        assert!(matches!(
+            plain.validate("service.org", &[1], at("2021-09-30T16:20:00Z")),
+            Err(SiweError::NotYetValid)
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_or_malformed_fields() {
+        let cases = [
+            EIP_MESSAGE.replace("Nonce: 32891756\n", ""),
+            EIP_MESSAGE.replace("Issued At: 2021-09-30T16:25:24Z\n", ""),
+            EIP_MESSAGE.replace("URI: https://service.org/login\n", ""),
+            EIP_MESSAGE.replace("Chain ID: 1\n", ""),
+            EIP_MESSAGE.replace("Version: 1", "Version: 2"),
+            EIP_MESSAGE.replace("Chain ID: 1", "Chain ID: um"),
+            EIP_MESSAGE.replace("Nonce: 32891756", "Nonce: 123"),
+            EIP_MESSAGE.replace("2021-09-30T16:25:24Z", "ontem"),
+            EIP_MESSAGE.replace("Version: 1", "Version: 1\nVersion: 1"),
+            EIP_MESSAGE.replace(" wants you to sign in", " quer que você entre"),
+            EIP_MESSAGE.replace("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23", "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"),
+            EIP_MESSAGE.replace("- https://", "https://"),
+            String::new(),
+        ];
+        for message in cases {
+            assert!(
+                matches!(message.parse::<SiweMessage>(), Err(SiweError::Malformed(_))),
+                "{}",
+                message
+            );
+        }
+    }
+}
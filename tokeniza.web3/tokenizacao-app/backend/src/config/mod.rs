@@ -6,18 +6,55 @@ pub struct AppConfig {
     pub server: ServerConfig,
     pub blockchain: BlockchainConfig,
     pub database: DatabaseConfig,
+    pub indexer: IndexerConfig,
+    pub auth: AuthConfig,
+    pub storage: StorageConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    pub public_url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockchainConfig {
-    pub rpc_url: String,
+    pub networks: Vec<NetworkConfig>,
+    pub default_network: String,
+    pub rpc_max_retries: u32,
+    pub rpc_backoff_ms: u64,
+    pub health_check_interval_secs: u64,
+    pub ws_reconnects: usize,
+    pub confirmations: u64,
+    pub tx_poll_interval_secs: u64,
+    /// Tempo desde o registro antes de uma compra que o nó não conhece ser
+    /// dada como descartada.
+    pub tx_drop_after_secs: u64,
+    /// Quanto uma requisição espera pelo recibo antes de devolver a operação
+    /// como pendente.
+    pub tx_wait_timeout_secs: u64,
+    pub stuck_after_blocks: u64,
+    pub fee_bump_percent: u64,
+    pub max_fee_per_gas_gwei: u64,
+}
+
+/// Uma rede nomeada (`sepolia`, `zksync`, `anvil`...) com seu próprio nó,
+/// contratos e carteira de operação.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    pub name: String,
+    /// Endpoints em ordem de preferência; os seguintes só atendem quando os
+    /// anteriores falham.
+    pub rpc_urls: Vec<String>,
+    /// Endpoints `ws://`/`wss://` de `RPC_URL`, usados para assinar novos
+    /// blocos e logs dos contratos e, sem `rpc_urls`, para as requisições.
+    pub ws_urls: Vec<String>,
+    /// Quantos endpoints precisam concordar em leituras críticas (saldos e
+    /// recibos); 1 desliga a verificação.
+    pub rpc_quorum: usize,
     pub chain_id: u64,
+    #[serde(skip_serializing)]
     pub private_key: Option<String>,
     pub contract_addresses: ContractAddresses,
 }
@@ -34,36 +71,326 @@ pub struct DatabaseConfig {
     pub url: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerConfig {
+    pub enabled: bool,
+    pub start_block: Option<u64>,
+    pub batch_size: u64,
+    pub poll_interval_secs: u64,
+    pub reorg_depth: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Domínio que as mensagens SIWE precisam declarar (`app.exemplo.com`).
+    pub siwe_domain: String,
+    pub nonce_ttl_secs: u64,
+    pub session_ttl_secs: u64,
+    /// Carteiras que recebem o papel `Admin` na inicialização.
+    pub admin_addresses: Vec<String>,
+    /// Limite padrão das chaves de API criadas sem um limite próprio.
+    pub api_key_rate_limit_per_minute: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// Tamanho máximo de cada arquivo enviado.
+    pub max_upload_bytes: usize,
+}
+
+/// Onde os arquivos dos ativos são gravados (`STORAGE_BACKEND`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StorageBackend {
+    Local { dir: String },
+    S3(S3Config),
+}
+
+/// Qualquer serviço compatível com S3 (AWS, MinIO...), acessado com URLs
+/// no estilo `<endpoint>/<bucket>/<chave>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    #[serde(skip_serializing)]
+    pub secret_access_key: String,
+}
+
+impl StorageConfig {
+    fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let var = |key: &str| env::var(key).ok().filter(|v| !v.is_empty());
+        let required = |key: &str| var(key).ok_or_else(|| format!("{} é obrigatório com STORAGE_BACKEND=s3", key));
+
+        let backend = match var("STORAGE_BACKEND").as_deref().unwrap_or("local") {
+            "local" => StorageBackend::Local {
+                dir: var("STORAGE_LOCAL_DIR").unwrap_or_else(|| "./uploads".to_string()),
+            },
+            "s3" => StorageBackend::S3(S3Config {
+                endpoint: required("S3_ENDPOINT")?.trim_end_matches('/').to_string(),
+                bucket: required("S3_BUCKET")?,
+                region: var("S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+                access_key_id: required("S3_ACCESS_KEY_ID")?,
+                secret_access_key: required("S3_SECRET_ACCESS_KEY")?,
+            }),
+            other => return Err(format!("STORAGE_BACKEND deve ser local ou s3, recebido '{}'", other).into()),
+        };
+
+        Ok(Self {
+            backend,
+            max_upload_bytes: var("UPLOAD_MAX_BYTES")
+                .unwrap_or_else(|| "10485760".to_string())
+                .parse()?,
+        })
+    }
+}
+
+impl BlockchainConfig {
+    /// Rede usada pelas escritas e pelos serviços em segundo plano.
+    pub fn default_network(&self) -> &NetworkConfig {
+        self.networks
+            .iter()
+            .find(|network| network.name == self.default_network)
+            .unwrap_or(&self.networks[0])
+    }
+}
+
+impl NetworkConfig {
+    /// Endpoints que atendem as requisições: os HTTP ou, numa rede só com
+    /// WebSocket, os `ws://`/`wss://`.
+    pub fn request_urls(&self) -> &[String] {
+        if self.rpc_urls.is_empty() {
+            &self.ws_urls
+        } else {
+            &self.rpc_urls
+        }
+    }
+
+    /// Lê as variáveis `<PREFIXO>RPC_URL` (uma ou mais URLs HTTP ou WebSocket
+    /// separadas por vírgula), `<PREFIXO>RPC_QUORUM`, `<PREFIXO>CHAIN_ID`, `<PREFIXO>PRIVATE_KEY`
+    /// e `<PREFIXO>*_ADDRESS`; o prefixo vazio corresponde às variáveis legadas.
+    fn from_env(name: &str, prefix: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let var = |key: &str| env::var(format!("{}{}", prefix, key)).ok().filter(|v| !v.is_empty());
+
+        let (ws_urls, rpc_urls): (Vec<String>, Vec<String>) = var("RPC_URL")
+            .unwrap_or_else(|| "http://localhost:8545".to_string())
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .partition(|url| url.starts_with("ws://") || url.starts_with("wss://"));
+        // Sem URL http(s), as requisições também vão pelo WebSocket.
+        let request_urls = if rpc_urls.is_empty() { ws_urls.len() } else { rpc_urls.len() };
+        if request_urls == 0 {
+            return Err(format!("{}RPC_URL precisa de ao menos uma URL", prefix).into());
+        }
+        let rpc_quorum: usize = var("RPC_QUORUM")
+            .unwrap_or_else(|| "1".to_string())
+            .parse()
+            .map_err(|_| format!("{}RPC_QUORUM inválido", prefix))?;
+        if rpc_quorum == 0 || rpc_quorum > request_urls {
+            return Err(format!(
+                "{}RPC_QUORUM deve estar entre 1 e o número de URLs de requisição em {}RPC_URL ({})",
+                prefix, prefix, request_urls
+            )
+            .into());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            rpc_urls,
+            ws_urls,
+            rpc_quorum,
+            chain_id: var("CHAIN_ID")
+                .unwrap_or_else(|| "1337".to_string())
+                .parse()
+                .map_err(|_| format!("{}CHAIN_ID inválido", prefix))?,
+            private_key: var("PRIVATE_KEY"),
+            contract_addresses: ContractAddresses {
+                asset_token: var("ASSET_TOKEN_ADDRESS"),
+                marketplace: var("MARKETPLACE_ADDRESS"),
+                waitlist: var("WAITLIST_ADDRESS"),
+            },
+        })
+    }
+}
+
+/// `NETWORKS=sepolia,zksync` define as redes, cada uma configurada por
+/// variáveis com o nome em maiúsculas como prefixo (`SEPOLIA_RPC_URL`...).
+/// Sem `NETWORKS`, `RPC_URL`, `CHAIN_ID` e afins formam uma única rede `local`.
+fn networks_from_env() -> Result<Vec<NetworkConfig>, Box<dyn std::error::Error>> {
+    let names: Vec<String> = env::var("NETWORKS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    if names.is_empty() {
+        return Ok(vec![NetworkConfig::from_env("local", "")?]);
+    }
+
+    let mut networks: Vec<NetworkConfig> = Vec::new();
+    for name in names {
+        let prefix = format!("{}_", name.to_uppercase().replace('-', "_"));
+        let network = NetworkConfig::from_env(&name, &prefix)?;
+        if networks.iter().any(|other| other.name == network.name || other.chain_id == network.chain_id) {
+            return Err(format!("Rede '{}' repetida em NETWORKS (nome ou chain id)", name).into());
+        }
+        networks.push(network);
+    }
+
+    Ok(networks)
+}
+
 impl AppConfig {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         dotenv::dotenv().ok();
 
+        let port: u16 = env::var("PORT")
+            .unwrap_or_else(|_| "8080".to_string())
+            .parse()?;
+
+        let networks = networks_from_env()?;
+        let default_network = match env::var("DEFAULT_NETWORK").ok().filter(|name| !name.is_empty()) {
+            Some(name) if networks.iter().any(|network| network.name == name) => name,
+            Some(name) => return Err(format!("DEFAULT_NETWORK '{}' não está em NETWORKS", name).into()),
+            None => networks[0].name.clone(),
+        };
+
+        let public_url = env::var("PUBLIC_URL")
+            .unwrap_or_else(|_| format!("http://localhost:{}", port));
+        // Sem SIWE_DOMAIN, vale o host (com porta) da URL pública.
+        let siwe_domain = env::var("SIWE_DOMAIN")
+            .ok()
+            .filter(|domain| !domain.is_empty())
+            .unwrap_or_else(|| {
+                let without_scheme = public_url.split_once("://").map_or(public_url.as_str(), |(_, rest)| rest);
+                without_scheme.split('/').next().unwrap_or_default().to_string()
+            });
+
         let config = Self {
             server: ServerConfig {
                 host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
-                port: env::var("PORT")
-                    .unwrap_or_else(|_| "8080".to_string())
-                    .parse()?,
+                port,
+                public_url,
             },
             blockchain: BlockchainConfig {
-                rpc_url: env::var("RPC_URL")
-                    .unwrap_or_else(|_| "http://localhost:8545".to_string()),
-                chain_id: env::var("CHAIN_ID")
-                    .unwrap_or_else(|_| "1337".to_string())
-                    .parse()?,
-                private_key: env::var("PRIVATE_KEY").ok(),
-                contract_addresses: ContractAddresses {
-                    asset_token: env::var("ASSET_TOKEN_ADDRESS").ok(),
-                    marketplace: env::var("MARKETPLACE_ADDRESS").ok(),
-                    waitlist: env::var("WAITLIST_ADDRESS").ok(),
-                },
+                networks,
+                default_network,
+                rpc_max_retries: env::var("RPC_MAX_RETRIES")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()?,
+                rpc_backoff_ms: env::var("RPC_BACKOFF_MS")
+                    .unwrap_or_else(|_| "250".to_string())
+                    .parse()?,
+                health_check_interval_secs: env::var("HEALTH_CHECK_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "15".to_string())
+                    .parse()?,
+                ws_reconnects: env::var("WS_RECONNECTS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()?,
+                confirmations: env::var("TX_CONFIRMATIONS")
+                    .unwrap_or_else(|_| "1".to_string())
+                    .parse()?,
+                tx_poll_interval_secs: env::var("TX_POLL_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()?,
+                tx_drop_after_secs: env::var("TX_DROP_AFTER_SECS")
+                    .unwrap_or_else(|_| "600".to_string())
+                    .parse()?,
+                tx_wait_timeout_secs: env::var("TX_WAIT_TIMEOUT_SECS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()?,
+                stuck_after_blocks: env::var("TX_STUCK_AFTER_BLOCKS")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()?,
+                fee_bump_percent: env::var("TX_FEE_BUMP_PERCENT")
+                    .unwrap_or_else(|_| "20".to_string())
+                    .parse()?,
+                max_fee_per_gas_gwei: env::var("TX_MAX_FEE_GWEI")
+                    .unwrap_or_else(|_| "200".to_string())
+                    .parse()?,
             },
             database: DatabaseConfig {
                 url: env::var("DATABASE_URL")
                     .unwrap_or_else(|_| "sqlite:./tokenizacao.db".to_string()),
             },
+            indexer: IndexerConfig {
+                enabled: env::var("INDEXER_ENABLED")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()?,
+                start_block: env::var("INDEXER_START_BLOCK")
+                    .ok()
+                    .filter(|block| !block.is_empty())
+                    .map(|block| block.parse())
+                    .transpose()?,
+                batch_size: env::var("INDEXER_BATCH_SIZE")
+                    .unwrap_or_else(|_| "500".to_string())
+                    .parse()?,
+                poll_interval_secs: env::var("INDEXER_POLL_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()?,
+                reorg_depth: env::var("INDEXER_REORG_DEPTH")
+                    .unwrap_or_else(|_| "64".to_string())
+                    .parse()?,
+            },
+            auth: AuthConfig {
+                siwe_domain,
+                nonce_ttl_secs: env::var("SIWE_NONCE_TTL_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()?,
+                session_ttl_secs: env::var("SESSION_TTL_SECS")
+                    .unwrap_or_else(|_| "86400".to_string())
+                    .parse()?,
+                admin_addresses: env::var("ADMIN_ADDRESSES")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|address| address.trim().to_lowercase())
+                    .filter(|address| !address.is_empty())
+                    .collect(),
+                api_key_rate_limit_per_minute: env::var("API_KEY_RATE_LIMIT_PER_MINUTE")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()?,
+            },
+            storage: StorageConfig::from_env()?,
         };
 
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cada teste usa um prefixo próprio: as variáveis de ambiente são do
+    // processo inteiro.
+    #[test]
+    fn ws_only_rpc_url_serves_requests_over_the_websocket() {
+        env::set_var("WSONLY_RPC_URL", "wss://node.example/ws");
+        env::set_var("WSONLY_RPC_QUORUM", "1");
+
+        let network = NetworkConfig::from_env("wsonly", "WSONLY_").unwrap();
+        assert!(network.rpc_urls.is_empty());
+        assert_eq!(network.ws_urls, ["wss://node.example/ws"]);
+        assert_eq!(network.request_urls(), ["wss://node.example/ws"]);
+
+        env::set_var("WSONLY_RPC_QUORUM", "2");
+        assert!(NetworkConfig::from_env("wsonly", "WSONLY_").is_err());
+    }
+
+    #[test]
+    fn http_urls_serve_requests_when_both_are_given() {
+        env::set_var("MIXED_RPC_URL", "http://a:8545, ws://a:8546 ,http://b:8545");
+        env::set_var("MIXED_RPC_QUORUM", "2");
+
+        let network = NetworkConfig::from_env("mixed", "MIXED_").unwrap();
+        assert_eq!(network.request_urls(), ["http://a:8545", "http://b:8545"]);
+        assert_eq!(network.ws_urls, ["ws://a:8546"]);
+
+        env::set_var("MIXED_RPC_URL", " , ");
+        assert!(NetworkConfig::from_env("mixed", "MIXED_").is_err());
+    }
+}
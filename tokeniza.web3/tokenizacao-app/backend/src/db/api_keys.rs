@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    format_timestamp, from_db_int, parse_timestamp, parse_uuid, DbPool, RepositoryError,
+};
+use crate::models::ApiKey;
+
+const API_KEY_COLUMNS: &str = "id, name, prefix, address, scopes, rate_limit_per_minute, \
+     created_by, created_at, last_used_at, revoked_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct ApiKeyRow {
+    id: String,
+    name: String,
+    prefix: String,
+    address: String,
+    scopes: String,
+    rate_limit_per_minute: i64,
+    created_by: String,
+    created_at: String,
+    last_used_at: Option<String>,
+    revoked_at: Option<String>,
+}
+
+impl TryFrom<ApiKeyRow> for ApiKey {
+    type Error = RepositoryError;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self, Self::Error> {
+        Ok(ApiKey {
+            id: parse_uuid(&row.id)?,
+            name: row.name,
+            prefix: row.prefix,
+            address: row.address,
+            scopes: serde_json::from_str(&row.scopes)?,
+            rate_limit_per_minute: u32::try_from(from_db_int(row.rate_limit_per_minute)?)
+                .map_err(|_| RepositoryError::Corrupted(format!("limite {}", row.rate_limit_per_minute)))?,
+            created_by: row.created_by,
+            created_at: parse_timestamp(&row.created_at)?,
+            last_used_at: row.last_used_at.as_deref().map(parse_timestamp).transpose()?,
+            revoked_at: row.revoked_at.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pool: DbPool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, key: &ApiKey, key_hash: &str) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO api_keys (key_hash, {}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+            API_KEY_COLUMNS
+        ))
+        .bind(key_hash)
+        .bind(key.id.to_string())
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.address)
+        .bind(serde_json::to_string(&key.scopes)?)
+        .bind(i64::from(key.rate_limit_per_minute))
+        .bind(&key.created_by)
+        .bind(format_timestamp(&key.created_at))
+        .bind(key.last_used_at.as_ref().map(format_timestamp))
+        .bind(key.revoked_at.as_ref().map(format_timestamp))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list(&self) -> Result<Vec<ApiKey>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "SELECT {} FROM api_keys ORDER BY created_at DESC",
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(ApiKey::try_from).collect()
+    }
+
+    /// Chave não revogada com este hash.
+    pub async fn find_active(&self, key_hash: &str) -> Result<Option<ApiKey>, RepositoryError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(&format!(
+            "SELECT {} FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+            API_KEY_COLUMNS
+        ))
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(ApiKey::try_from).transpose()
+    }
+
+    pub async fn touch(&self, id: Uuid, used_at: &DateTime<Utc>) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE api_keys SET last_used_at = $2 WHERE id = $1")
+            .bind(id.to_string())
+            .bind(format_timestamp(used_at))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Devolve `false` se a chave não existe ou já estava revogada.
+    pub async fn revoke(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("UPDATE api_keys SET revoked_at = $2 WHERE id = $1 AND revoked_at IS NULL")
+            .bind(id.to_string())
+            .bind(format_timestamp(&crate::db::now()))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,133 @@
+use uuid::Uuid;
+
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, parse_timestamp, parse_uuid, Db,
+    RepositoryError,
+};
+use crate::models::AssetStatusChange;
+
+const HISTORY_COLUMNS: &str = "id, asset_id, from_status, to_status, changed_by, reason, created_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct AssetStatusChangeRow {
+    id: String,
+    asset_id: String,
+    from_status: Option<String>,
+    to_status: String,
+    changed_by: String,
+    reason: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<AssetStatusChangeRow> for AssetStatusChange {
+    type Error = RepositoryError;
+
+    fn try_from(row: AssetStatusChangeRow) -> Result<Self, Self::Error> {
+        Ok(AssetStatusChange {
+            id: parse_uuid(&row.id)?,
+            asset_id: parse_uuid(&row.asset_id)?,
+            from_status: row.from_status.as_deref().map(enum_from_db).transpose()?,
+            to_status: enum_from_db(&row.to_status)?,
+            changed_by: row.changed_by,
+            reason: row.reason,
+            created_at: parse_timestamp(&row.created_at)?,
+        })
+    }
+}
+
+/// Histórico de status dos ativos; só recebe inserções, exceto quando um
+/// reorg apaga o próprio ativo.
+#[derive(Clone)]
+pub struct AssetHistoryRepository {
+    db: Db,
+}
+
+impl AssetHistoryRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn record(&self, change: &AssetStatusChange) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO asset_status_history ({}) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            HISTORY_COLUMNS
+        ))
+        .bind(change.id.to_string())
+        .bind(change.asset_id.to_string())
+        .bind(change.from_status.as_ref().map(enum_to_db).transpose()?)
+        .bind(enum_to_db(&change.to_status)?)
+        .bind(&change.changed_by)
+        .bind(&change.reason)
+        .bind(format_timestamp(&change.created_at))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_by_asset(&self, asset_id: Uuid) -> Result<Vec<AssetStatusChange>, RepositoryError> {
+        let rows = sqlx::query_as::<_, AssetStatusChangeRow>(&format!(
+            "SELECT {} FROM asset_status_history WHERE asset_id = $1 ORDER BY created_at ASC",
+            HISTORY_COLUMNS
+        ))
+        .bind(asset_id.to_string())
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(AssetStatusChange::try_from).collect()
+    }
+
+    pub async fn delete_by_asset(&self, asset_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM asset_status_history WHERE asset_id = $1")
+            .bind(asset_id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_asset, test_pool, AssetRepository};
+    use crate::models::AssetStatus;
+
+    #[tokio::test]
+    async fn history_requires_an_existing_asset() {
+        let pool = test_pool().await;
+        let history = AssetHistoryRepository::new(pool.clone());
+
+        let orphan = AssetStatusChange::new(Uuid::new_v4(), None, AssetStatus::Draft, "0xabc", None);
+        assert!(history.record(&orphan).await.is_err());
+
+        let asset = test_asset("0xabc");
+        AssetRepository::new(pool.clone()).create(&asset).await.unwrap();
+        history
+            .record(&AssetStatusChange::new(asset.id, None, asset.status, "0xabc", None))
+            .await
+            .unwrap();
+        assert_eq!(history.list_by_asset(asset.id).await.unwrap().len(), 1);
+
+        // O ativo só pode sair depois do histórico.
+        assert!(AssetRepository::new(pool.clone()).delete(asset.id).await.is_err());
+        history.delete_by_asset(asset.id).await.unwrap();
+        AssetRepository::new(pool).delete(asset.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn id_cannot_be_null() {
+        let pool = test_pool().await;
+        let asset = test_asset("0xabc");
+        AssetRepository::new(pool.clone()).create(&asset).await.unwrap();
+
+        let result = sqlx::query(
+            "INSERT INTO asset_status_history (id, asset_id, to_status, changed_by, created_at) \
+             VALUES (NULL, $1, 'Draft', '0xabc', '2024-01-01T00:00:00Z')",
+        )
+        .bind(asset.id.to_string())
+        .execute(&pool)
+        .await;
+        assert!(result.is_err());
+    }
+}
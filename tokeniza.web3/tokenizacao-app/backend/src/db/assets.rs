@@ -0,0 +1,652 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::pagination::{contains_pattern, search_text, Bind, Conditions, Cursor, Page};
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::{Asset, AssetQuery, AssetSort, AssetType, SortOrder};
+use crate::units::Wei;
+
+const ASSET_COLUMNS: &str = "id, name, description, asset_type, value, total_supply, \
+     available_supply, token_address, token_id, owner, metadata, status, created_at, updated_at, \
+     deleted_at, chain_id";
+
+#[derive(Debug, sqlx::FromRow)]
+struct AssetRow {
+    id: String,
+    name: String,
+    description: String,
+    asset_type: String,
+    value: String,
+    total_supply: i64,
+    available_supply: i64,
+    token_address: Option<String>,
+    token_id: Option<String>,
+    owner: String,
+    metadata: String,
+    status: String,
+    created_at: String,
+    updated_at: String,
+    deleted_at: Option<String>,
+    chain_id: Option<i64>,
+}
+
+impl TryFrom<AssetRow> for Asset {
+    type Error = RepositoryError;
+
+    fn try_from(row: AssetRow) -> Result<Self, Self::Error> {
+        Ok(Asset {
+            id: parse_uuid(&row.id)?,
+            name: row.name,
+            description: row.description,
+            asset_type: enum_from_db(&row.asset_type)?,
+            value: row
+                .value
+                .parse()
+                .map_err(|_| RepositoryError::Corrupted(format!("value '{}'", row.value)))?,
+            total_supply: from_db_int(row.total_supply)?,
+            available_supply: from_db_int(row.available_supply)?,
+            token_address: row.token_address,
+            token_id: row.token_id,
+            owner: row.owner,
+            metadata: serde_json::from_str(&row.metadata)?,
+            status: enum_from_db(&row.status)?,
+            created_at: parse_timestamp(&row.created_at)?,
+            updated_at: parse_timestamp(&row.updated_at)?,
+            deleted_at: row.deleted_at.as_deref().map(parse_timestamp).transpose()?,
+            chain_id: row.chain_id.map(from_db_int).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct AssetRepository {
+    db: Db,
+}
+
+impl AssetRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn create(&self, asset: &Asset) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO assets ({}, location, search_name, search_description, search_location) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, \
+             $18, $19, $20)",
+            ASSET_COLUMNS
+        ))
+        .bind(asset.id.to_string())
+        .bind(&asset.name)
+        .bind(&asset.description)
+        .bind(enum_to_db(&asset.asset_type)?)
+        .bind(asset.value.to_string())
+        .bind(to_db_int(asset.total_supply)?)
+        .bind(to_db_int(asset.available_supply)?)
+        .bind(&asset.token_address)
+        .bind(&asset.token_id)
+        .bind(&asset.owner)
+        .bind(serde_json::to_string(&asset.metadata)?)
+        .bind(enum_to_db(&asset.status)?)
+        .bind(format_timestamp(&asset.created_at))
+        .bind(format_timestamp(&asset.updated_at))
+        .bind(asset.deleted_at.as_ref().map(format_timestamp))
+        .bind(asset.chain_id.map(to_db_int).transpose()?)
+        .bind(&asset.metadata.location)
+        .bind(search_text(&asset.name))
+        .bind(search_text(&asset.description))
+        .bind(asset.metadata.location.as_deref().map(search_text))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Grava apenas se o `updated_at` no banco ainda for `expected_updated_at`
+    /// (concorrência otimista); devolve `false` se outra escrita veio antes.
+    pub async fn update_if_unmodified(
+        &self,
+        asset: &Asset,
+        expected_updated_at: &DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE assets SET name = $2, description = $3, asset_type = $4, value = $5, \
+             total_supply = $6, available_supply = $7, token_address = $8, token_id = $9, \
+             owner = $10, metadata = $11, status = $12, updated_at = $13, location = $14, \
+             deleted_at = $15, search_name = $16, search_description = $17, \
+             search_location = $18, chain_id = $20 WHERE id = $1 AND updated_at = $19",
+        )
+        .bind(asset.id.to_string())
+        .bind(&asset.name)
+        .bind(&asset.description)
+        .bind(enum_to_db(&asset.asset_type)?)
+        .bind(asset.value.to_string())
+        .bind(to_db_int(asset.total_supply)?)
+        .bind(to_db_int(asset.available_supply)?)
+        .bind(&asset.token_address)
+        .bind(&asset.token_id)
+        .bind(&asset.owner)
+        .bind(serde_json::to_string(&asset.metadata)?)
+        .bind(enum_to_db(&asset.status)?)
+        .bind(format_timestamp(&asset.updated_at))
+        .bind(&asset.metadata.location)
+        .bind(asset.deleted_at.as_ref().map(format_timestamp))
+        .bind(search_text(&asset.name))
+        .bind(search_text(&asset.description))
+        .bind(asset.metadata.location.as_deref().map(search_text))
+        .bind(format_timestamp(expected_updated_at))
+        .bind(asset.chain_id.map(to_db_int).transpose()?)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Relê o ativo, aplica `change` e grava com `update_if_unmodified`,
+    /// repetindo se outra escrita chegou no meio: os campos que `change` não
+    /// toca ficam com o valor mais recente, e edições concorrentes não se
+    /// perdem. Devolve o ativo antes e depois, ou `None` se ele não existe.
+    pub async fn modify<E>(
+        &self,
+        id: Uuid,
+        mut change: impl FnMut(&mut Asset) -> Result<(), E>,
+    ) -> Result<Option<(Asset, Asset)>, E>
+    where
+        E: From<RepositoryError>,
+    {
+        loop {
+            let Some(previous) = self.find_by_id(id).await? else {
+                return Ok(None);
+            };
+            let mut asset = previous.clone();
+            change(&mut asset)?;
+            asset.updated_at = super::next_updated_at(previous.updated_at);
+
+            if self.update_if_unmodified(&asset, &previous.updated_at).await? {
+                return Ok(Some((previous, asset)));
+            }
+        }
+    }
+
+    /// Remove a linha de fato; usado apenas ao desfazer um reorg. Ativos
+    /// excluídos pela API recebem `deleted_at`.
+    pub async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM assets WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Ativos excluídos pela API não são encontrados.
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Asset>, RepositoryError> {
+        let row = sqlx::query_as::<_, AssetRow>(&format!(
+            "SELECT {} FROM assets WHERE id = $1 AND deleted_at IS NULL",
+            ASSET_COLUMNS
+        ))
+        .bind(id.to_string())
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Asset::try_from).transpose()
+    }
+
+    /// Ativos com token na rede informada, para consultas on-chain.
+    pub async fn list_tokenized(&self, chain_id: u64) -> Result<Vec<Asset>, RepositoryError> {
+        let rows = sqlx::query_as::<_, AssetRow>(&format!(
+            "SELECT {} FROM assets \
+             WHERE chain_id = $1 AND token_address IS NOT NULL AND deleted_at IS NULL \
+             ORDER BY created_at DESC",
+            ASSET_COLUMNS
+        ))
+        .bind(to_db_int(chain_id)?)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(Asset::try_from).collect()
+    }
+
+    /// O mesmo contrato pode existir em mais de uma rede; o token é buscado
+    /// na rede informada.
+    pub async fn find_by_token(
+        &self,
+        chain_id: u64,
+        token_address: &str,
+        token_id: &str,
+    ) -> Result<Option<Asset>, RepositoryError> {
+        let row = sqlx::query_as::<_, AssetRow>(&format!(
+            "SELECT {} FROM assets \
+             WHERE chain_id = $1 AND LOWER(token_address) = LOWER($2) AND token_id = $3",
+            ASSET_COLUMNS
+        ))
+        .bind(to_db_int(chain_id)?)
+        .bind(token_address)
+        .bind(token_id)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Asset::try_from).transpose()
+    }
+
+    /// Busca paginada por cursor (keyset): cada página continua a partir do
+    /// último item da anterior, sem `OFFSET`.
+    pub async fn search(
+        &self,
+        query: &AssetQuery,
+        limit: u32,
+        after: Option<&Cursor>,
+    ) -> Result<Page<Asset>, RepositoryError> {
+        let mut conditions = Conditions::default();
+        conditions.push("deleted_at IS NULL".to_string());
+
+        if let Some(asset_type) = &query.asset_type {
+            let placeholder = conditions.bind(Bind::Text(enum_to_db(&AssetType::from_label(asset_type))?));
+            conditions.push(format!("asset_type = {}", placeholder));
+        }
+        if let Some(status) = &query.status {
+            let placeholder = conditions.bind(Bind::Text(enum_to_db(status)?));
+            conditions.push(format!("status = {}", placeholder));
+        }
+        if let Some(owner) = &query.owner {
+            let placeholder = conditions.bind(Bind::Text(owner.to_lowercase()));
+            conditions.push(format!("LOWER(owner) = {}", placeholder));
+        }
+        if let Some(min_value) = query.min_value {
+            let clause = compare_value(&mut conditions, ">", min_value);
+            conditions.push(clause);
+        }
+        if let Some(max_value) = query.max_value {
+            let clause = compare_value(&mut conditions, "<", max_value);
+            conditions.push(clause);
+        }
+        if let Some(location) = &query.location {
+            let placeholder = conditions.bind(Bind::Text(contains_pattern(location)));
+            conditions.push(format!("search_location LIKE {} ESCAPE '\\'", placeholder));
+        }
+        if let Some(created_from) = &query.created_from {
+            let placeholder = conditions.bind(Bind::Text(format_timestamp(created_from)));
+            conditions.push(format!("created_at >= {}", placeholder));
+        }
+        if let Some(created_to) = &query.created_to {
+            let placeholder = conditions.bind(Bind::Text(format_timestamp(created_to)));
+            conditions.push(format!("created_at <= {}", placeholder));
+        }
+        if let Some(text) = query.q.as_deref().map(str::trim).filter(|text| !text.is_empty()) {
+            let placeholder = conditions.bind(Bind::Text(contains_pattern(text)));
+            conditions.push(format!(
+                "(search_name LIKE {0} ESCAPE '\\' OR search_description LIKE {0} ESCAPE '\\')",
+                placeholder
+            ));
+        }
+
+        let (op, direction) = match query.order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+        // Valores em wei são texto decimal sem zeros à esquerda: comparar o
+        // comprimento antes do texto dá a ordem numérica.
+        let sort_column = match query.sort {
+            AssetSort::CreatedAt => "created_at",
+            AssetSort::UpdatedAt => "updated_at",
+            AssetSort::Name => "name",
+            AssetSort::Value => "value",
+        };
+        let order_by = if query.sort == AssetSort::Value {
+            format!("LENGTH(value) {0}, value {0}, id {0}", direction)
+        } else {
+            format!("{0} {1}, id {1}", sort_column, direction)
+        };
+
+        if let Some(cursor) = after {
+            let key = conditions.bind(Bind::Text(cursor.key.clone()));
+            let id = conditions.bind(Bind::Text(cursor.id.to_string()));
+            let after_key = format!(
+                "({0} {1} {2} OR ({0} = {2} AND id {1} {3}))",
+                sort_column, op, key, id
+            );
+            if query.sort == AssetSort::Value {
+                let length = conditions.bind(Bind::Int(cursor.key.len() as i64));
+                conditions.push(format!(
+                    "(LENGTH(value) {0} {1} OR (LENGTH(value) = {1} AND {2}))",
+                    op, length, after_key
+                ));
+            } else {
+                conditions.push(after_key);
+            }
+        }
+
+        let where_clause = conditions.where_clause();
+        let mut binds = conditions.into_binds();
+        // Um item a mais indica se existe próxima página.
+        binds.push(Bind::Int(i64::from(limit) + 1));
+        let sql = format!(
+            "SELECT {} FROM assets{} ORDER BY {} LIMIT ${}",
+            ASSET_COLUMNS,
+            where_clause,
+            order_by,
+            binds.len()
+        );
+
+        let mut statement = sqlx::query_as::<_, AssetRow>(&sql);
+        for bind in binds {
+            statement = match bind {
+                Bind::Text(value) => statement.bind(value),
+                Bind::Int(value) => statement.bind(value),
+            };
+        }
+        let mut items = statement
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?
+            .into_iter()
+            .map(Asset::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let next_cursor = if items.len() > limit as usize {
+            items.truncate(limit as usize);
+            items.last().map(|last| {
+                Cursor {
+                    sort: enum_to_db(&query.sort).unwrap_or_default(),
+                    order: enum_to_db(&query.order).unwrap_or_default(),
+                    key: sort_key(last, query.sort),
+                    id: last.id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items,
+            limit,
+            next_cursor,
+        })
+    }
+
+    /// Preenche `location` e as colunas `search_*` em ativos gravados antes
+    /// delas existirem. Devolve quantos foram atualizados.
+    pub async fn backfill_search_columns(&self) -> Result<u64, RepositoryError> {
+        let rows = sqlx::query_as::<_, AssetRow>(&format!(
+            "SELECT {} FROM assets WHERE search_name IS NULL",
+            ASSET_COLUMNS
+        ))
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        let mut updated = 0;
+        for row in rows {
+            let asset = Asset::try_from(row)?;
+            sqlx::query(
+                "UPDATE assets SET location = $2, search_name = $3, search_description = $4, \
+                 search_location = $5 WHERE id = $1",
+            )
+            .bind(asset.id.to_string())
+            .bind(&asset.metadata.location)
+            .bind(search_text(&asset.name))
+            .bind(search_text(&asset.description))
+            .bind(asset.metadata.location.as_deref().map(search_text))
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+            updated += 1;
+        }
+
+        Ok(updated)
+    }
+}
+
+/// `value` maior (`>`) ou menor (`<`) que `bound`, ou igual a ele.
+fn compare_value(conditions: &mut Conditions, op: &str, bound: Wei) -> String {
+    let bound = bound.to_string();
+    let length = conditions.bind(Bind::Int(bound.len() as i64));
+    let value = conditions.bind(Bind::Text(bound));
+    format!(
+        "(LENGTH(value) {0} {1} OR (LENGTH(value) = {1} AND value {0}= {2}))",
+        op, length, value
+    )
+}
+
+fn sort_key(asset: &Asset, sort: AssetSort) -> String {
+    match sort {
+        AssetSort::CreatedAt => format_timestamp(&asset.created_at),
+        AssetSort::UpdatedAt => format_timestamp(&asset.updated_at),
+        AssetSort::Name => asset.name.clone(),
+        AssetSort::Value => asset.value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_asset, test_pool};
+
+    async fn seed(repository: &AssetRepository, entries: &[(&str, &str, u64)]) -> Vec<Asset> {
+        let mut assets = Vec::new();
+        for (name, location, value) in entries {
+            let mut asset = test_asset("0xabc");
+            asset.name = name.to_string();
+            asset.metadata.location = Some(location.to_string());
+            asset.value = Wei::from(*value);
+            repository.create(&asset).await.unwrap();
+            assets.push(asset);
+        }
+        assets
+    }
+
+    /// Percorre todas as páginas seguindo `next_cursor`, como um cliente.
+    async fn walk(repository: &AssetRepository, query: &AssetQuery, limit: u32) -> Vec<Page<Asset>> {
+        let mut pages = Vec::new();
+        let mut cursor: Option<Cursor> = None;
+        loop {
+            let page = repository.search(query, limit, cursor.as_ref()).await.unwrap();
+            let next = page.next_cursor.clone();
+            pages.push(page);
+            match next {
+                Some(raw) => {
+                    cursor = Some(
+                        Cursor::decode_for(
+                            &raw,
+                            &enum_to_db(&query.sort).unwrap(),
+                            &enum_to_db(&query.order).unwrap(),
+                        )
+                        .expect("cursor da mesma ordenação"),
+                    );
+                }
+                None => return pages,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn search_ignores_unicode_case() {
+        let repository = AssetRepository::new(test_pool().await);
+        seed(
+            &repository,
+            &[("ÁTICO NO CENTRO", "SÃO PAULO, SP", 1), ("Casa de praia", "Ubatuba, SP", 2)],
+        )
+        .await;
+
+        let by_location = AssetQuery {
+            location: Some("São Paulo".to_string()),
+            ..Default::default()
+        };
+        let page = repository.search(&by_location, 10, None).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].name, "ÁTICO NO CENTRO");
+
+        let by_text = AssetQuery {
+            q: Some("ático".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(repository.search(&by_text, 10, None).await.unwrap().items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn backfill_fills_search_columns() {
+        let pool = test_pool().await;
+        let repository = AssetRepository::new(pool.clone());
+        seed(&repository, &[("ÉDIFICE", "BRASÍLIA", 1)]).await;
+        sqlx::query("UPDATE assets SET location = NULL, search_name = NULL, search_location = NULL")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(repository.backfill_search_columns().await.unwrap(), 1);
+        assert_eq!(repository.backfill_search_columns().await.unwrap(), 0);
+        let query = AssetQuery {
+            location: Some("brasília".to_string()),
+            q: Some("édifice".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(repository.search(&query, 10, None).await.unwrap().items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn cursor_walks_every_ordering_without_gaps() {
+        let repository = AssetRepository::new(test_pool().await);
+        // Nomes e valores repetidos exercitam o desempate por id; 9 e 10
+        // diferem no comprimento do texto em wei.
+        let assets = seed(
+            &repository,
+            &[
+                ("Casa", "Recife", 10),
+                ("Apartamento", "Recife", 9),
+                ("Casa", "Recife", 10),
+                ("Terreno", "Recife", 1_000),
+                ("Galpão", "Recife", 9),
+            ],
+        )
+        .await;
+
+        for sort in [AssetSort::CreatedAt, AssetSort::UpdatedAt, AssetSort::Name, AssetSort::Value] {
+            for order in [SortOrder::Asc, SortOrder::Desc] {
+                let query = AssetQuery { sort, order, ..Default::default() };
+                let everything = repository.search(&query, 100, None).await.unwrap();
+                assert_eq!(everything.items.len(), assets.len());
+                assert!(everything.next_cursor.is_none());
+
+                for limit in [1, 2, 5] {
+                    let pages = walk(&repository, &query, limit).await;
+                    let walked: Vec<Uuid> =
+                        pages.iter().flat_map(|page| page.items.iter().map(|asset| asset.id)).collect();
+                    let expected: Vec<Uuid> = everything.items.iter().map(|asset| asset.id).collect();
+                    assert_eq!(walked, expected, "{:?} {:?} limite {}", sort, order, limit);
+
+                    // Só a última página vem sem cursor, mesmo quando o total
+                    // é múltiplo do limite.
+                    let last = pages.last().unwrap();
+                    assert!(last.next_cursor.is_none());
+                    assert!(!last.items.is_empty());
+                    assert!(pages[..pages.len() - 1].iter().all(|page| page.next_cursor.is_some()));
+                }
+            }
+        }
+
+        let by_value = AssetQuery { sort: AssetSort::Value, order: SortOrder::Asc, ..Default::default() };
+        let values: Vec<String> = repository
+            .search(&by_value, 100, None)
+            .await
+            .unwrap()
+            .items
+            .iter()
+            .map(|asset| asset.value.to_string())
+            .collect();
+        assert_eq!(values, ["9", "9", "10", "10", "1000"]);
+    }
+
+    #[tokio::test]
+    async fn search_skips_deleted_assets() {
+        let repository = AssetRepository::new(test_pool().await);
+        let mut assets = seed(&repository, &[("Casa", "Recife", 1), ("Loja", "Recife", 2)]).await;
+        let updated_at = assets[0].updated_at;
+        assets[0].deleted_at = Some(crate::db::now());
+        assert!(repository.update_if_unmodified(&assets[0], &updated_at).await.unwrap());
+
+        let page = repository.search(&AssetQuery::default(), 10, None).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, assets[1].id);
+        assert!(repository.find_by_id(assets[0].id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn list_tokenized_filters_by_chain() {
+        let repository = AssetRepository::new(test_pool().await);
+        let entries = [("A", "Recife", 1), ("B", "Recife", 2), ("C", "Recife", 3), ("D", "Recife", 4)];
+        let mut assets = seed(&repository, &entries).await;
+        for (asset, chain_id) in assets.iter_mut().zip([Some(1), Some(1), Some(5), None]) {
+            let updated_at = asset.updated_at;
+            asset.chain_id = chain_id;
+            asset.token_address = chain_id.map(|_| "0x00000000000000000000000000000000000000aa".to_string());
+            assert!(repository.update_if_unmodified(asset, &updated_at).await.unwrap());
+        }
+        let updated_at = assets[1].updated_at;
+        assets[1].deleted_at = Some(crate::db::now());
+        assert!(repository.update_if_unmodified(&assets[1], &updated_at).await.unwrap());
+
+        let ids: Vec<Uuid> = repository
+            .list_tokenized(1)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|asset| asset.id)
+            .collect();
+        assert_eq!(ids, [assets[0].id]);
+        assert_eq!(repository.list_tokenized(5).await.unwrap()[0].id, assets[2].id);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn modify_keeps_concurrent_edits() {
+        let repository = AssetRepository::new(test_pool().await);
+        let asset = seed(&repository, &[("Casa", "Recife", 1)]).await.remove(0);
+
+        // Edição pela API entre a leitura do indexador e a escrita dele.
+        let mut edited = asset.clone();
+        edited.name = "Casa reformada".to_string();
+        edited.updated_at = crate::db::next_updated_at(asset.updated_at);
+        let mut raced = false;
+        let (previous, sold) = repository
+            .modify(asset.id, |current| {
+                if !raced {
+                    raced = true;
+                    let written = tokio::task::block_in_place(|| {
+                        tokio::runtime::Handle::current()
+                            .block_on(repository.update_if_unmodified(&edited, &asset.updated_at))
+                    });
+                    assert!(written.unwrap());
+                }
+                current.owner = "0xdef".to_string();
+                Ok::<_, RepositoryError>(())
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(previous.name, "Casa reformada");
+        assert_eq!(sold.owner, "0xdef");
+        let stored = repository.find_by_id(asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Casa reformada");
+        assert_eq!(stored.owner, "0xdef");
+        assert!(stored.updated_at > edited.updated_at);
+    }
+
+    #[tokio::test]
+    async fn modify_aborts_on_error_and_skips_missing_assets() {
+        let repository = AssetRepository::new(test_pool().await);
+        let asset = seed(&repository, &[("Casa", "Recife", 1)]).await.remove(0);
+
+        let refused = repository
+            .modify(asset.id, |_| Err(RepositoryError::Corrupted("recusado".to_string())))
+            .await;
+        assert!(refused.is_err());
+        let stored = repository.find_by_id(asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.updated_at, asset.updated_at);
+
+        let missing = repository
+            .modify(Uuid::new_v4(), |_| Ok::<_, RepositoryError>(()))
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::{ChainEffect, ChainEvent, IndexedBlock};
+use sqlx::Connection;
+
+#[derive(Debug, sqlx::FromRow)]
+struct IndexedBlockRow {
+    block_number: i64,
+    block_hash: String,
+    parent_hash: String,
+}
+
+impl TryFrom<IndexedBlockRow> for IndexedBlock {
+    type Error = RepositoryError;
+
+    fn try_from(row: IndexedBlockRow) -> Result<Self, Self::Error> {
+        Ok(IndexedBlock {
+            block_number: from_db_int(row.block_number)?,
+            block_hash: row.block_hash,
+            parent_hash: row.parent_hash,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ChainEffectRow {
+    id: String,
+    block_number: i64,
+    log_index: i64,
+    step: i64,
+    entity: String,
+    entity_id: String,
+    previous: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ChainEffectRow> for ChainEffect {
+    type Error = RepositoryError;
+
+    fn try_from(row: ChainEffectRow) -> Result<Self, Self::Error> {
+        Ok(ChainEffect {
+            id: parse_uuid(&row.id)?,
+            block_number: from_db_int(row.block_number)?,
+            log_index: from_db_int(row.log_index)?,
+            step: from_db_int(row.step)?,
+            entity: enum_from_db(&row.entity)?,
+            entity_id: parse_uuid(&row.entity_id)?,
+            previous: row.previous.as_deref().map(serde_json::from_str).transpose()?,
+            created_at: parse_timestamp(&row.created_at)?,
+        })
+    }
+}
+
+/// Estado do indexador de uma rede: eventos, blocos, efeitos e checkpoints
+/// de outras redes não são vistos.
+#[derive(Clone)]
+pub struct ChainEventRepository {
+    db: Db,
+    chain_id: i64,
+}
+
+impl ChainEventRepository {
+    pub fn new(db: impl Into<Db>, chain_id: u64) -> Self {
+        Self {
+            db: db.into(),
+            chain_id: chain_id as i64,
+        }
+    }
+
+    pub async fn exists(&self, tx_hash: &str, log_index: u64) -> Result<bool, RepositoryError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM chain_events WHERE chain_id = $3 AND tx_hash = $1 AND log_index = $2",
+        )
+        .bind(tx_hash)
+        .bind(to_db_int(log_index)?)
+        .bind(self.chain_id)
+        .fetch_one(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Grava o evento e devolve `false` se ele já havia sido indexado
+    /// (mesmo `tx_hash` e `log_index`).
+    pub async fn record(&self, event: &ChainEvent) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "INSERT INTO chain_events \
+             (id, block_number, tx_hash, log_index, contract, event, data, created_at, chain_id) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+             ON CONFLICT (chain_id, tx_hash, log_index) DO NOTHING",
+        )
+        .bind(event.id.to_string())
+        .bind(to_db_int(event.block_number)?)
+        .bind(&event.tx_hash)
+        .bind(to_db_int(event.log_index)?)
+        .bind(&event.contract)
+        .bind(&event.event)
+        .bind(event.data.to_string())
+        .bind(format_timestamp(&event.created_at))
+        .bind(self.chain_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn checkpoint(&self, name: &str) -> Result<Option<u64>, RepositoryError> {
+        let block = sqlx::query_scalar::<_, i64>(
+            "SELECT block_number FROM indexer_checkpoints WHERE chain_id = $1 AND name = $2",
+        )
+        .bind(self.chain_id)
+        .bind(name)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        block.map(from_db_int).transpose()
+    }
+
+    pub async fn save_checkpoint(&self, name: &str, block_number: u64) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO indexer_checkpoints (name, block_number, updated_at, chain_id) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (chain_id, name) DO UPDATE SET block_number = $2, updated_at = $3",
+        )
+        .bind(name)
+        .bind(to_db_int(block_number)?)
+        .bind(format_timestamp(&super::now()))
+        .bind(self.chain_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn save_block(&self, block: &IndexedBlock) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO indexed_blocks (block_number, block_hash, parent_hash, created_at, chain_id) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (chain_id, block_number) DO UPDATE SET block_hash = $2, parent_hash = $3, created_at = $4",
+        )
+        .bind(to_db_int(block.block_number)?)
+        .bind(&block.block_hash)
+        .bind(&block.parent_hash)
+        .bind(format_timestamp(&super::now()))
+        .bind(self.chain_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_block(&self, block_number: u64) -> Result<Option<IndexedBlock>, RepositoryError> {
+        let row = sqlx::query_as::<_, IndexedBlockRow>(
+            "SELECT block_number, block_hash, parent_hash FROM indexed_blocks \
+             WHERE chain_id = $2 AND block_number = $1",
+        )
+        .bind(to_db_int(block_number)?)
+        .bind(self.chain_id)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(IndexedBlock::try_from).transpose()
+    }
+
+    /// Blocos gravados até `block_number`, do mais recente para o mais antigo.
+    pub async fn blocks_until(&self, block_number: u64) -> Result<Vec<IndexedBlock>, RepositoryError> {
+        let rows = sqlx::query_as::<_, IndexedBlockRow>(
+            "SELECT block_number, block_hash, parent_hash FROM indexed_blocks \
+             WHERE chain_id = $2 AND block_number <= $1 ORDER BY block_number DESC",
+        )
+        .bind(to_db_int(block_number)?)
+        .bind(self.chain_id)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(IndexedBlock::try_from).collect()
+    }
+
+    pub async fn record_effect(&self, effect: &ChainEffect) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO chain_effects \
+             (id, block_number, log_index, step, entity, entity_id, previous, created_at, chain_id) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(effect.id.to_string())
+        .bind(to_db_int(effect.block_number)?)
+        .bind(to_db_int(effect.log_index)?)
+        .bind(to_db_int(effect.step)?)
+        .bind(enum_to_db(&effect.entity)?)
+        .bind(effect.entity_id.to_string())
+        .bind(effect.previous.as_ref().map(|previous| previous.to_string()))
+        .bind(format_timestamp(&effect.created_at))
+        .bind(self.chain_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Efeitos a partir de `block_number` na ordem em que devem ser desfeitos
+    /// (do último aplicado para o primeiro).
+    pub async fn effects_since(&self, block_number: u64) -> Result<Vec<ChainEffect>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ChainEffectRow>(
+            "SELECT id, block_number, log_index, step, entity, entity_id, previous, created_at \
+             FROM chain_effects WHERE chain_id = $2 AND block_number >= $1 \
+             ORDER BY block_number DESC, log_index DESC, created_at DESC, step DESC",
+        )
+        .bind(to_db_int(block_number)?)
+        .bind(self.chain_id)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(ChainEffect::try_from).collect()
+    }
+
+    /// Descarta eventos, efeitos e blocos a partir de `fork_block` e volta o
+    /// checkpoint para o bloco anterior, numa única transação (um savepoint,
+    /// se o repositório já está dentro de uma).
+    pub async fn rewind(&self, name: &str, fork_block: u64) -> Result<(), RepositoryError> {
+        let fork_block = to_db_int(fork_block)?;
+        let mut conn = self.db.conn().await?;
+        let mut tx = conn.begin().await?;
+
+        for table in ["chain_events", "chain_effects", "indexed_blocks"] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE chain_id = $2 AND block_number >= $1",
+                table
+            ))
+            .bind(fork_block)
+            .bind(self.chain_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            "UPDATE indexer_checkpoints SET block_number = $2, updated_at = $3 \
+             WHERE chain_id = $4 AND name = $1",
+        )
+        .bind(name)
+        .bind((fork_block - 1).max(0))
+        .bind(format_timestamp(&super::now()))
+        .bind(self.chain_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Remove blocos e efeitos anteriores a `block_number`, que já estão
+    /// além da profundidade de reorg considerada.
+    pub async fn prune(&self, block_number: u64) -> Result<(), RepositoryError> {
+        let block_number = to_db_int(block_number)?;
+
+        for table in ["chain_effects", "indexed_blocks"] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE chain_id = $2 AND block_number < $1",
+                table
+            ))
+            .bind(block_number)
+            .bind(self.chain_id)
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block_number: u64) -> ChainEvent {
+        ChainEvent {
+            id: uuid::Uuid::new_v4(),
+            block_number,
+            tx_hash: format!("0x{:064x}", 7),
+            log_index: 0,
+            contract: "AssetToken".to_string(),
+            event: "AssetMinted".to_string(),
+            data: serde_json::json!({}),
+            created_at: super::super::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn each_chain_has_its_own_events_blocks_and_checkpoints() {
+        let pool = crate::db::test_pool().await;
+        let anvil = ChainEventRepository::new(pool.clone(), 31337);
+        let sepolia = ChainEventRepository::new(pool, 11155111);
+
+        // Mesmo hash e log_index em redes diferentes são eventos distintos.
+        assert!(anvil.record(&event(10)).await.unwrap());
+        assert!(sepolia.record(&event(10)).await.unwrap());
+        assert!(!anvil.record(&event(10)).await.unwrap());
+
+        for repository in [&anvil, &sepolia] {
+            repository
+                .save_block(&IndexedBlock {
+                    block_number: 10,
+                    block_hash: "0xaa".to_string(),
+                    parent_hash: "0x99".to_string(),
+                })
+                .await
+                .unwrap();
+            repository.save_checkpoint("indexer", 10).await.unwrap();
+        }
+
+        anvil.rewind("indexer", 10).await.unwrap();
+
+        assert!(!anvil.exists(&event(10).tx_hash, 0).await.unwrap());
+        assert!(anvil.find_block(10).await.unwrap().is_none());
+        assert_eq!(anvil.checkpoint("indexer").await.unwrap(), Some(9));
+
+        assert!(sepolia.exists(&event(10).tx_hash, 0).await.unwrap());
+        assert!(sepolia.find_block(10).await.unwrap().is_some());
+        assert_eq!(sepolia.checkpoint("indexer").await.unwrap(), Some(10));
+    }
+}
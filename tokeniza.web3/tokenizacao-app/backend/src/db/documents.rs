@@ -0,0 +1,121 @@
+use uuid::Uuid;
+
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::AssetDocument;
+
+const DOCUMENT_COLUMNS: &str = "id, asset_id, kind, file_name, content_type, size, sha256, \
+     storage_key, uploaded_by, created_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct AssetDocumentRow {
+    id: String,
+    asset_id: String,
+    kind: String,
+    file_name: String,
+    content_type: String,
+    size: i64,
+    sha256: String,
+    storage_key: String,
+    uploaded_by: String,
+    created_at: String,
+}
+
+impl TryFrom<AssetDocumentRow> for AssetDocument {
+    type Error = RepositoryError;
+
+    fn try_from(row: AssetDocumentRow) -> Result<Self, Self::Error> {
+        Ok(AssetDocument {
+            id: parse_uuid(&row.id)?,
+            asset_id: parse_uuid(&row.asset_id)?,
+            kind: enum_from_db(&row.kind)?,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size: from_db_int(row.size)?,
+            sha256: row.sha256,
+            storage_key: row.storage_key,
+            uploaded_by: row.uploaded_by,
+            created_at: parse_timestamp(&row.created_at)?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct DocumentRepository {
+    db: Db,
+}
+
+impl DocumentRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn create(&self, document: &AssetDocument) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO asset_documents ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            DOCUMENT_COLUMNS
+        ))
+        .bind(document.id.to_string())
+        .bind(document.asset_id.to_string())
+        .bind(enum_to_db(&document.kind)?)
+        .bind(&document.file_name)
+        .bind(&document.content_type)
+        .bind(to_db_int(document.size)?)
+        .bind(&document.sha256)
+        .bind(&document.storage_key)
+        .bind(&document.uploaded_by)
+        .bind(format_timestamp(&document.created_at))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_by_asset(&self, asset_id: Uuid) -> Result<Vec<AssetDocument>, RepositoryError> {
+        let rows = sqlx::query_as::<_, AssetDocumentRow>(&format!(
+            "SELECT {} FROM asset_documents WHERE asset_id = $1 ORDER BY created_at ASC",
+            DOCUMENT_COLUMNS
+        ))
+        .bind(asset_id.to_string())
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(AssetDocument::try_from).collect()
+    }
+
+    pub async fn find(&self, asset_id: Uuid, id: Uuid) -> Result<Option<AssetDocument>, RepositoryError> {
+        let row = sqlx::query_as::<_, AssetDocumentRow>(&format!(
+            "SELECT {} FROM asset_documents WHERE asset_id = $1 AND id = $2",
+            DOCUMENT_COLUMNS
+        ))
+        .bind(asset_id.to_string())
+        .bind(id.to_string())
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(AssetDocument::try_from).transpose()
+    }
+
+    /// Remove os documentos do ativo e os devolve, para que os arquivos
+    /// também saiam do armazenamento.
+    pub async fn delete_by_asset(&self, asset_id: Uuid) -> Result<Vec<AssetDocument>, RepositoryError> {
+        let documents = self.list_by_asset(asset_id).await?;
+        sqlx::query("DELETE FROM asset_documents WHERE asset_id = $1")
+            .bind(asset_id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(documents)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM asset_documents WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use uuid::Uuid;
+
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::{Listing, ListingStatus};
+
+const LISTING_COLUMNS: &str =
+    "id, asset_id, seller, price, quantity, status, tx_hash, created_at, expires_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct ListingRow {
+    id: String,
+    asset_id: String,
+    seller: String,
+    price: String,
+    quantity: i64,
+    status: String,
+    tx_hash: Option<String>,
+    created_at: String,
+    expires_at: Option<String>,
+}
+
+impl TryFrom<ListingRow> for Listing {
+    type Error = RepositoryError;
+
+    fn try_from(row: ListingRow) -> Result<Self, Self::Error> {
+        Ok(Listing {
+            id: parse_uuid(&row.id)?,
+            asset_id: parse_uuid(&row.asset_id)?,
+            seller: row.seller,
+            price: row
+                .price
+                .parse()
+                .map_err(|_| RepositoryError::Corrupted(format!("price '{}'", row.price)))?,
+            quantity: from_db_int(row.quantity)?,
+            status: enum_from_db(&row.status)?,
+            tx_hash: row.tx_hash,
+            created_at: parse_timestamp(&row.created_at)?,
+            expires_at: row.expires_at.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ListingRepository {
+    db: Db,
+}
+
+impl ListingRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn create(&self, listing: &Listing) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO listings ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            LISTING_COLUMNS
+        ))
+        .bind(listing.id.to_string())
+        .bind(listing.asset_id.to_string())
+        .bind(&listing.seller)
+        .bind(listing.price.to_string())
+        .bind(to_db_int(listing.quantity)?)
+        .bind(enum_to_db(&listing.status)?)
+        .bind(&listing.tx_hash)
+        .bind(format_timestamp(&listing.created_at))
+        .bind(listing.expires_at.as_ref().map(format_timestamp))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update(&self, listing: &Listing) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE listings SET seller = $2, price = $3, quantity = $4, status = $5, \
+             tx_hash = $6, expires_at = $7 WHERE id = $1",
+        )
+        .bind(listing.id.to_string())
+        .bind(&listing.seller)
+        .bind(listing.price.to_string())
+        .bind(to_db_int(listing.quantity)?)
+        .bind(enum_to_db(&listing.status)?)
+        .bind(&listing.tx_hash)
+        .bind(listing.expires_at.as_ref().map(format_timestamp))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM listings WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_asset(&self, asset_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM listings WHERE asset_id = $1")
+            .bind(asset_id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Listing>, RepositoryError> {
+        let row = sqlx::query_as::<_, ListingRow>(&format!(
+            "SELECT {} FROM listings WHERE id = $1",
+            LISTING_COLUMNS
+        ))
+        .bind(id.to_string())
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Listing::try_from).transpose()
+    }
+
+    pub async fn find_by_tx_hash(&self, tx_hash: &str) -> Result<Option<Listing>, RepositoryError> {
+        let row = sqlx::query_as::<_, ListingRow>(&format!(
+            "SELECT {} FROM listings WHERE LOWER(tx_hash) = LOWER($1)",
+            LISTING_COLUMNS
+        ))
+        .bind(tx_hash)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Listing::try_from).transpose()
+    }
+
+    pub async fn find_active_by_asset(&self, asset_id: Uuid) -> Result<Option<Listing>, RepositoryError> {
+        let row = sqlx::query_as::<_, ListingRow>(&format!(
+            "SELECT {} FROM listings WHERE asset_id = $1 AND status = $2 \
+             ORDER BY created_at DESC LIMIT 1",
+            LISTING_COLUMNS
+        ))
+        .bind(asset_id.to_string())
+        .bind(enum_to_db(&ListingStatus::Active)?)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Listing::try_from).transpose()
+    }
+
+    pub async fn list(&self) -> Result<Vec<Listing>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ListingRow>(&format!(
+            "SELECT {} FROM listings ORDER BY created_at DESC",
+            LISTING_COLUMNS
+        ))
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(Listing::try_from).collect()
+    }
+}
@@ -0,0 +1,138 @@
+use ethers::utils::hex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Posição logo após o último item de uma página: o valor da coluna de
+/// ordenação e o id, que desempata itens com o mesmo valor. Viaja para o
+/// cliente como uma string opaca.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Campo e sentido da ordenação em que o cursor foi gerado; não vale
+    /// para outra.
+    pub sort: String,
+    pub order: String,
+    pub key: String,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = hex::decode(raw).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Lê o cursor e confere que ele foi gerado com a mesma ordenação.
+    pub fn decode_for(raw: &str, sort: &str, order: &str) -> Option<Self> {
+        Self::decode(raw).filter(|cursor| cursor.sort == sort && cursor.order == order)
+    }
+}
+
+/// Uma página de resultados; `next_cursor` é `None` na última.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub limit: u32,
+    pub next_cursor: Option<String>,
+}
+
+/// Valor de um parâmetro em consultas montadas em tempo de execução.
+pub(crate) enum Bind {
+    Text(String),
+    Int(i64),
+}
+
+/// Acumula condições `WHERE` numerando os placeholders (`$1`, `$2`, ...) na
+/// ordem em que os valores são adicionados.
+#[derive(Default)]
+pub(crate) struct Conditions {
+    clauses: Vec<String>,
+    binds: Vec<Bind>,
+}
+
+impl Conditions {
+    /// Registra um valor e devolve o seu placeholder.
+    pub fn bind(&mut self, value: Bind) -> String {
+        self.binds.push(value);
+        format!("${}", self.binds.len())
+    }
+
+    pub fn push(&mut self, clause: String) {
+        self.clauses.push(clause);
+    }
+
+    pub fn where_clause(&self) -> String {
+        if self.clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.clauses.join(" AND "))
+        }
+    }
+
+    pub fn into_binds(self) -> Vec<Bind> {
+        self.binds
+    }
+}
+
+/// Forma gravada nas colunas `search_*`. O `LOWER()` do SQLite só conhece
+/// ASCII, então a caixa é normalizada aqui, igual para o texto gravado e
+/// para o termo buscado.
+pub(crate) fn search_text(text: &str) -> String {
+    text.to_lowercase()
+}
+
+/// Padrão `LIKE` para "contém", com `%`, `_` e `\` escapados
+/// (usar com `ESCAPE '\'`) e comparável às colunas de `search_text`.
+pub(crate) fn contains_pattern(term: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in search_text(term).chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> Cursor {
+        Cursor {
+            sort: "name".to_string(),
+            order: "asc".to_string(),
+            key: "São Paulo \"centro\"".to_string(),
+            id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = cursor();
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor.clone()));
+        assert_eq!(Cursor::decode_for(&cursor.encode(), "name", "asc"), Some(cursor));
+    }
+
+    #[test]
+    fn cursor_is_bound_to_its_ordering() {
+        let encoded = cursor().encode();
+        assert_eq!(Cursor::decode_for(&encoded, "name", "desc"), None);
+        assert_eq!(Cursor::decode_for(&encoded, "value", "asc"), None);
+        assert_eq!(Cursor::decode_for("zz", "name", "asc"), None);
+        assert_eq!(Cursor::decode_for(&hex::encode("{}"), "name", "asc"), None);
+    }
+
+    #[test]
+    fn pattern_escapes_wildcards_and_folds_unicode_case() {
+        assert_eq!(contains_pattern("SÃO Paulo"), "%são paulo%");
+        assert_eq!(contains_pattern("50%_a\\b"), "%50\\%\\_a\\\\b%");
+    }
+}
@@ -0,0 +1,94 @@
+use super::{enum_from_db, enum_to_db, format_timestamp, parse_timestamp, DbPool, RepositoryError};
+use crate::models::{Role, RoleGrant};
+
+const ROLE_COLUMNS: &str = "address, role, granted_by, created_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct RoleGrantRow {
+    address: String,
+    role: String,
+    granted_by: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<RoleGrantRow> for RoleGrant {
+    type Error = RepositoryError;
+
+    fn try_from(row: RoleGrantRow) -> Result<Self, Self::Error> {
+        Ok(RoleGrant {
+            address: row.address,
+            role: enum_from_db(&row.role)?,
+            granted_by: row.granted_by,
+            created_at: parse_timestamp(&row.created_at)?,
+        })
+    }
+}
+
+/// Endereços são gravados em minúsculas, como nas sessões.
+#[derive(Clone)]
+pub struct RoleRepository {
+    pool: DbPool,
+}
+
+impl RoleRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn roles_of(&self, address: &str) -> Result<Vec<Role>, RepositoryError> {
+        self.grants_of(address)
+            .await
+            .map(|grants| grants.into_iter().map(|grant| grant.role).collect())
+    }
+
+    pub async fn grants_of(&self, address: &str) -> Result<Vec<RoleGrant>, RepositoryError> {
+        let rows = sqlx::query_as::<_, RoleGrantRow>(&format!(
+            "SELECT {} FROM user_roles WHERE address = $1 ORDER BY created_at ASC",
+            ROLE_COLUMNS
+        ))
+        .bind(address.to_lowercase())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(RoleGrant::try_from).collect()
+    }
+
+    pub async fn list(&self) -> Result<Vec<RoleGrant>, RepositoryError> {
+        let rows = sqlx::query_as::<_, RoleGrantRow>(&format!(
+            "SELECT {} FROM user_roles ORDER BY address ASC, created_at ASC",
+            ROLE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(RoleGrant::try_from).collect()
+    }
+
+    /// Devolve `false` se o endereço já tinha o papel.
+    pub async fn grant(&self, grant: &RoleGrant) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(&format!(
+            "INSERT INTO user_roles ({}) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (address, role) DO NOTHING",
+            ROLE_COLUMNS
+        ))
+        .bind(grant.address.to_lowercase())
+        .bind(enum_to_db(&grant.role)?)
+        .bind(&grant.granted_by)
+        .bind(format_timestamp(&grant.created_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Devolve `false` se o endereço não tinha o papel.
+    pub async fn revoke(&self, address: &str, role: Role) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM user_roles WHERE address = $1 AND role = $2")
+            .bind(address.to_lowercase())
+            .bind(enum_to_db(&role)?)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    format_timestamp, from_db_int, parse_timestamp, parse_uuid, to_db_int, DbPool,
+    RepositoryError,
+};
+use crate::models::Session;
+
+const SESSION_COLUMNS: &str = "id, address, chain_id, created_at, expires_at, revoked_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct SessionRow {
+    id: String,
+    address: String,
+    chain_id: i64,
+    created_at: String,
+    expires_at: String,
+    revoked_at: Option<String>,
+}
+
+impl TryFrom<SessionRow> for Session {
+    type Error = RepositoryError;
+
+    fn try_from(row: SessionRow) -> Result<Self, Self::Error> {
+        Ok(Session {
+            id: parse_uuid(&row.id)?,
+            address: row.address,
+            chain_id: from_db_int(row.chain_id)?,
+            created_at: parse_timestamp(&row.created_at)?,
+            expires_at: parse_timestamp(&row.expires_at)?,
+            revoked_at: row.revoked_at.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct SessionRepository {
+    pool: DbPool,
+}
+
+impl SessionRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_nonce(&self, nonce: &str, expires_at: &DateTime<Utc>) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO auth_nonces (nonce, created_at, expires_at) VALUES ($1, $2, $3)")
+            .bind(nonce)
+            .bind(format_timestamp(&crate::db::now()))
+            .bind(format_timestamp(expires_at))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Apaga o nonce e devolve `true` se ele existia e ainda não havia
+    /// expirado; um nonce só serve para um login.
+    pub async fn consume_nonce(&self, nonce: &str) -> Result<bool, RepositoryError> {
+        let now = format_timestamp(&crate::db::now());
+        let result = sqlx::query("DELETE FROM auth_nonces WHERE nonce = $1 AND expires_at > $2")
+            .bind(nonce)
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+
+        // Aproveita para descartar os que expiraram sem uso.
+        sqlx::query("DELETE FROM auth_nonces WHERE expires_at <= $1")
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn create(&self, session: &Session, token_hash: &str) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO sessions (token_hash, {}) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            SESSION_COLUMNS
+        ))
+        .bind(token_hash)
+        .bind(session.id.to_string())
+        .bind(&session.address)
+        .bind(to_db_int(session.chain_id)?)
+        .bind(format_timestamp(&session.created_at))
+        .bind(format_timestamp(&session.expires_at))
+        .bind(session.revoked_at.as_ref().map(format_timestamp))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sessão ainda válida (não expirada nem encerrada) do token.
+    pub async fn find_active(&self, token_hash: &str) -> Result<Option<Session>, RepositoryError> {
+        let row = sqlx::query_as::<_, SessionRow>(&format!(
+            "SELECT {} FROM sessions WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > $2",
+            SESSION_COLUMNS
+        ))
+        .bind(token_hash)
+        .bind(format_timestamp(&crate::db::now()))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Session::try_from).transpose()
+    }
+
+    pub async fn revoke(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE sessions SET revoked_at = $2 WHERE id = $1 AND revoked_at IS NULL")
+            .bind(id.to_string())
+            .bind(format_timestamp(&crate::db::now()))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn nonce_is_consumed_only_once() {
+        let sessions = SessionRepository::new(crate::db::test_pool().await);
+        let in_five_minutes = crate::db::now() + chrono::Duration::minutes(5);
+        sessions.create_nonce("32891756abc", &in_five_minutes).await.unwrap();
+
+        assert!(sessions.consume_nonce("32891756abc").await.unwrap());
+        assert!(!sessions.consume_nonce("32891756abc").await.unwrap());
+        assert!(!sessions.consume_nonce("desconhecido").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn expired_nonce_is_refused() {
+        let sessions = SessionRepository::new(crate::db::test_pool().await);
+        let past = crate::db::now() - chrono::Duration::seconds(1);
+        sessions.create_nonce("expirado01", &past).await.unwrap();
+
+        assert!(!sessions.consume_nonce("expirado01").await.unwrap());
+    }
+}
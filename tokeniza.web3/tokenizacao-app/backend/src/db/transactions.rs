@@ -0,0 +1,187 @@
+use uuid::Uuid;
+
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::{Transaction, TransactionStatus};
+
+const TRANSACTION_COLUMNS: &str = "id, listing_id, buyer, seller, price, quantity, tx_hash, \
+     status, created_at, completed_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct TransactionRow {
+    id: String,
+    listing_id: String,
+    buyer: String,
+    seller: String,
+    price: String,
+    quantity: i64,
+    tx_hash: Option<String>,
+    status: String,
+    created_at: String,
+    completed_at: Option<String>,
+}
+
+impl TryFrom<TransactionRow> for Transaction {
+    type Error = RepositoryError;
+
+    fn try_from(row: TransactionRow) -> Result<Self, Self::Error> {
+        Ok(Transaction {
+            id: parse_uuid(&row.id)?,
+            listing_id: parse_uuid(&row.listing_id)?,
+            buyer: row.buyer,
+            seller: row.seller,
+            price: row
+                .price
+                .parse()
+                .map_err(|_| RepositoryError::Corrupted(format!("price '{}'", row.price)))?,
+            quantity: from_db_int(row.quantity)?,
+            tx_hash: row.tx_hash,
+            status: enum_from_db(&row.status)?,
+            created_at: parse_timestamp(&row.created_at)?,
+            completed_at: row.completed_at.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TransactionRepository {
+    db: Db,
+}
+
+impl TransactionRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn create(&self, transaction: &Transaction) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO transactions ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(transaction.id.to_string())
+        .bind(transaction.listing_id.to_string())
+        .bind(&transaction.buyer)
+        .bind(&transaction.seller)
+        .bind(transaction.price.to_string())
+        .bind(to_db_int(transaction.quantity)?)
+        .bind(&transaction.tx_hash)
+        .bind(enum_to_db(&transaction.status)?)
+        .bind(format_timestamp(&transaction.created_at))
+        .bind(transaction.completed_at.as_ref().map(format_timestamp))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update(&self, transaction: &Transaction) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE transactions SET tx_hash = $2, status = $3, completed_at = $4 WHERE id = $1",
+        )
+        .bind(transaction.id.to_string())
+        .bind(&transaction.tx_hash)
+        .bind(enum_to_db(&transaction.status)?)
+        .bind(transaction.completed_at.as_ref().map(format_timestamp))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM transactions WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_listing(&self, listing_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM transactions WHERE listing_id = $1")
+            .bind(listing_id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove as compras de todas as ofertas do ativo.
+    pub async fn delete_by_asset(&self, asset_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM transactions WHERE listing_id IN (SELECT id FROM listings WHERE asset_id = $1)")
+            .bind(asset_id.to_string())
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, RepositoryError> {
+        let row = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM transactions WHERE id = $1",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(id.to_string())
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Transaction::try_from).transpose()
+    }
+
+    pub async fn find_by_tx_hash(&self, tx_hash: &str) -> Result<Option<Transaction>, RepositoryError> {
+        let row = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM transactions WHERE LOWER(tx_hash) = LOWER($1)",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(tx_hash)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(Transaction::try_from).transpose()
+    }
+
+    /// Transações de ofertas cujo ativo foi emitido em `chain_id`.
+    pub async fn list_by_status(
+        &self,
+        chain_id: u64,
+        status: &TransactionStatus,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let rows = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM transactions WHERE status = $1 AND listing_id IN \
+             (SELECT l.id FROM listings l JOIN assets a ON a.id = l.asset_id WHERE a.chain_id = $2) \
+             ORDER BY created_at",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(enum_to_db(status)?)
+        .bind(to_db_int(chain_id)?)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(Transaction::try_from).collect()
+    }
+
+    pub async fn has_pending_for_listing(&self, listing_id: Uuid) -> Result<bool, RepositoryError> {
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM transactions WHERE listing_id = $1 AND status = $2",
+        )
+        .bind(listing_id.to_string())
+        .bind(enum_to_db(&TransactionStatus::Pending)?)
+        .fetch_one(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(pending > 0)
+    }
+
+    pub async fn list(&self) -> Result<Vec<Transaction>, RepositoryError> {
+        let rows = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM transactions ORDER BY created_at DESC",
+            TRANSACTION_COLUMNS
+        ))
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(Transaction::try_from).collect()
+    }
+}
@@ -0,0 +1,227 @@
+use uuid::Uuid;
+
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, from_db_int, parse_timestamp, parse_uuid,
+    to_db_int, Db, RepositoryError,
+};
+use crate::models::{OperationStatus, TxAttempt, TxOperation};
+
+const OPERATION_COLUMNS: &str = "id, kind, nonce, tx_hash, status, created_at, updated_at, chain_id";
+
+const ATTEMPT_COLUMNS: &str = "id, operation_id, tx_hash, reason, max_fee_per_gas, \
+     max_priority_fee_per_gas, payload, submitted_block, created_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct OperationRow {
+    id: String,
+    kind: String,
+    nonce: i64,
+    tx_hash: String,
+    status: String,
+    created_at: String,
+    updated_at: String,
+    chain_id: i64,
+}
+
+impl TryFrom<OperationRow> for TxOperation {
+    type Error = RepositoryError;
+
+    fn try_from(row: OperationRow) -> Result<Self, Self::Error> {
+        Ok(TxOperation {
+            id: parse_uuid(&row.id)?,
+            kind: enum_from_db(&row.kind)?,
+            chain_id: from_db_int(row.chain_id)?,
+            nonce: from_db_int(row.nonce)?,
+            tx_hash: row.tx_hash,
+            status: enum_from_db(&row.status)?,
+            created_at: parse_timestamp(&row.created_at)?,
+            updated_at: parse_timestamp(&row.updated_at)?,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AttemptRow {
+    id: String,
+    operation_id: String,
+    tx_hash: String,
+    reason: String,
+    max_fee_per_gas: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+    payload: String,
+    submitted_block: i64,
+    created_at: String,
+}
+
+impl TryFrom<AttemptRow> for TxAttempt {
+    type Error = RepositoryError;
+
+    fn try_from(row: AttemptRow) -> Result<Self, Self::Error> {
+        Ok(TxAttempt {
+            id: parse_uuid(&row.id)?,
+            operation_id: parse_uuid(&row.operation_id)?,
+            tx_hash: row.tx_hash,
+            reason: enum_from_db(&row.reason)?,
+            max_fee_per_gas: row.max_fee_per_gas,
+            max_priority_fee_per_gas: row.max_priority_fee_per_gas,
+            payload: serde_json::from_str(&row.payload)?,
+            submitted_block: from_db_int(row.submitted_block)?,
+            created_at: parse_timestamp(&row.created_at)?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TxOperationRepository {
+    db: Db,
+}
+
+impl TxOperationRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    pub async fn create(&self, operation: &TxOperation) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO tx_operations ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            OPERATION_COLUMNS
+        ))
+        .bind(operation.id.to_string())
+        .bind(enum_to_db(&operation.kind)?)
+        .bind(to_db_int(operation.nonce)?)
+        .bind(&operation.tx_hash)
+        .bind(enum_to_db(&operation.status)?)
+        .bind(format_timestamp(&operation.created_at))
+        .bind(format_timestamp(&operation.updated_at))
+        .bind(to_db_int(operation.chain_id)?)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update(&self, operation: &TxOperation) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE tx_operations SET tx_hash = $2, status = $3, updated_at = $4 WHERE id = $1",
+        )
+        .bind(operation.id.to_string())
+        .bind(&operation.tx_hash)
+        .bind(enum_to_db(&operation.status)?)
+        .bind(format_timestamp(&operation.updated_at))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn add_attempt(&self, attempt: &TxAttempt) -> Result<(), RepositoryError> {
+        sqlx::query(&format!(
+            "INSERT INTO tx_attempts ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            ATTEMPT_COLUMNS
+        ))
+        .bind(attempt.id.to_string())
+        .bind(attempt.operation_id.to_string())
+        .bind(&attempt.tx_hash)
+        .bind(enum_to_db(&attempt.reason)?)
+        .bind(&attempt.max_fee_per_gas)
+        .bind(&attempt.max_priority_fee_per_gas)
+        .bind(attempt.payload.to_string())
+        .bind(to_db_int(attempt.submitted_block)?)
+        .bind(format_timestamp(&attempt.created_at))
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<TxOperation>, RepositoryError> {
+        let row = sqlx::query_as::<_, OperationRow>(&format!(
+            "SELECT {} FROM tx_operations WHERE id = $1",
+            OPERATION_COLUMNS
+        ))
+        .bind(id.to_string())
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(TxOperation::try_from).transpose()
+    }
+
+    pub async fn find_pending_by_nonce(
+        &self,
+        chain_id: u64,
+        nonce: u64,
+    ) -> Result<Option<TxOperation>, RepositoryError> {
+        let row = sqlx::query_as::<_, OperationRow>(&format!(
+            "SELECT {} FROM tx_operations WHERE chain_id = $3 AND nonce = $1 AND status = $2 \
+             ORDER BY created_at DESC LIMIT 1",
+            OPERATION_COLUMNS
+        ))
+        .bind(to_db_int(nonce)?)
+        .bind(enum_to_db(&OperationStatus::Pending)?)
+        .bind(to_db_int(chain_id)?)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(TxOperation::try_from).transpose()
+    }
+
+    /// Operação a que pertence uma tentativa, inclusive reenvios e cancelamentos.
+    pub async fn find_by_attempt_hash(
+        &self,
+        chain_id: u64,
+        tx_hash: &str,
+    ) -> Result<Option<TxOperation>, RepositoryError> {
+        let row = sqlx::query_as::<_, OperationRow>(
+            "SELECT o.id, o.kind, o.nonce, o.tx_hash, o.status, o.created_at, o.updated_at, o.chain_id \
+             FROM tx_operations o JOIN tx_attempts a ON a.operation_id = o.id \
+             WHERE o.chain_id = $2 AND LOWER(a.tx_hash) = LOWER($1)",
+        )
+        .bind(tx_hash)
+        .bind(to_db_int(chain_id)?)
+        .fetch_optional(&mut *self.db.conn().await?)
+        .await?;
+
+        row.map(TxOperation::try_from).transpose()
+    }
+
+    /// Tentativas da operação, da primeira para a mais recente.
+    pub async fn attempts(&self, operation_id: Uuid) -> Result<Vec<TxAttempt>, RepositoryError> {
+        let rows = sqlx::query_as::<_, AttemptRow>(&format!(
+            "SELECT {} FROM tx_attempts WHERE operation_id = $1 ORDER BY created_at ASC",
+            ATTEMPT_COLUMNS
+        ))
+        .bind(operation_id.to_string())
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(TxAttempt::try_from).collect()
+    }
+
+    pub async fn list_by_status(
+        &self,
+        chain_id: u64,
+        status: &OperationStatus,
+    ) -> Result<Vec<TxOperation>, RepositoryError> {
+        let rows = sqlx::query_as::<_, OperationRow>(&format!(
+            "SELECT {} FROM tx_operations WHERE chain_id = $2 AND status = $1 ORDER BY nonce ASC",
+            OPERATION_COLUMNS
+        ))
+        .bind(enum_to_db(status)?)
+        .bind(to_db_int(chain_id)?)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(TxOperation::try_from).collect()
+    }
+
+    pub async fn list(&self) -> Result<Vec<TxOperation>, RepositoryError> {
+        let rows = sqlx::query_as::<_, OperationRow>(&format!(
+            "SELECT {} FROM tx_operations ORDER BY created_at DESC",
+            OPERATION_COLUMNS
+        ))
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+
+        rows.into_iter().map(TxOperation::try_from).collect()
+    }
+}
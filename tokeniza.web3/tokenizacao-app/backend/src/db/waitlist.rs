@@ -0,0 +1,153 @@
+use super::{
+    enum_from_db, enum_to_db, format_timestamp, parse_timestamp, parse_uuid, DbPool,
+    RepositoryError,
+};
+use crate::models::{WaitlistEntry, WaitlistStats, WaitlistStatus};
+
+const WAITLIST_COLUMNS: &str =
+    "id, email, name, interest_areas, investment_range, status, created_at, contacted_at";
+
+#[derive(Debug, sqlx::FromRow)]
+struct WaitlistEntryRow {
+    id: String,
+    email: String,
+    name: Option<String>,
+    interest_areas: String,
+    investment_range: Option<String>,
+    status: String,
+    created_at: String,
+    contacted_at: Option<String>,
+}
+
+impl TryFrom<WaitlistEntryRow> for WaitlistEntry {
+    type Error = RepositoryError;
+
+    fn try_from(row: WaitlistEntryRow) -> Result<Self, Self::Error> {
+        Ok(WaitlistEntry {
+            id: parse_uuid(&row.id)?,
+            email: row.email,
+            name: row.name,
+            interest_areas: serde_json::from_str(&row.interest_areas)?,
+            investment_range: row.investment_range.as_deref().map(enum_from_db).transpose()?,
+            status: enum_from_db(&row.status)?,
+            created_at: parse_timestamp(&row.created_at)?,
+            contacted_at: row.contacted_at.as_deref().map(parse_timestamp).transpose()?,
+        })
+    }
+}
+
+/// E-mails são gravados em minúsculas; cada um entra na lista uma vez só.
+#[derive(Clone)]
+pub struct WaitlistRepository {
+    pool: DbPool,
+}
+
+impl WaitlistRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Devolve `false` se o e-mail já estava na lista.
+    pub async fn create(&self, entry: &WaitlistEntry) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(&format!(
+            "INSERT INTO waitlist_entries ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+             ON CONFLICT (email) DO NOTHING",
+            WAITLIST_COLUMNS
+        ))
+        .bind(entry.id.to_string())
+        .bind(entry.email.to_lowercase())
+        .bind(&entry.name)
+        .bind(serde_json::to_string(&entry.interest_areas)?)
+        .bind(entry.investment_range.as_ref().map(enum_to_db).transpose()?)
+        .bind(enum_to_db(&entry.status)?)
+        .bind(format_timestamp(&entry.created_at))
+        .bind(entry.contacted_at.as_ref().map(format_timestamp))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn list(&self) -> Result<Vec<WaitlistEntry>, RepositoryError> {
+        let rows = sqlx::query_as::<_, WaitlistEntryRow>(&format!(
+            "SELECT {} FROM waitlist_entries ORDER BY created_at DESC",
+            WAITLIST_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(WaitlistEntry::try_from).collect()
+    }
+
+    pub async fn stats(&self) -> Result<WaitlistStats, RepositoryError> {
+        let mut stats = WaitlistStats::default();
+        for entry in self.list().await? {
+            stats.total_entries += 1;
+            match entry.status {
+                WaitlistStatus::Pending => stats.pending += 1,
+                WaitlistStatus::Contacted => stats.contacted += 1,
+                WaitlistStatus::Converted => stats.converted += 1,
+                WaitlistStatus::Unsubscribed => stats.unsubscribed += 1,
+            }
+            for area in entry.interest_areas {
+                *stats.by_interest.entry(area).or_default() += 1;
+            }
+            if let Some(range) = &entry.investment_range {
+                *stats.by_investment_range.entry(enum_to_db(range)?).or_default() += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InvestmentRange;
+
+    fn entry(email: &str) -> WaitlistEntry {
+        WaitlistEntry {
+            id: uuid::Uuid::new_v4(),
+            email: email.to_string(),
+            name: None,
+            interest_areas: vec!["Art".to_string()],
+            investment_range: Some(InvestmentRange::Range10K50K),
+            status: WaitlistStatus::Pending,
+            created_at: crate::db::now(),
+            contacted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn each_email_enters_once() {
+        let waitlist = WaitlistRepository::new(crate::db::test_pool().await);
+
+        assert!(waitlist.create(&entry("ana@example.com")).await.unwrap());
+        assert!(!waitlist.create(&entry("ANA@example.com")).await.unwrap());
+
+        let entries = waitlist.list().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].interest_areas, vec!["Art".to_string()]);
+        assert!(matches!(entries[0].investment_range, Some(InvestmentRange::Range10K50K)));
+    }
+
+    #[tokio::test]
+    async fn stats_count_the_stored_entries() {
+        let waitlist = WaitlistRepository::new(crate::db::test_pool().await);
+        waitlist.create(&entry("ana@example.com")).await.unwrap();
+        let mut other = entry("bia@example.com");
+        other.interest_areas.push("RealEstate".to_string());
+        other.investment_range = None;
+        other.status = WaitlistStatus::Contacted;
+        waitlist.create(&other).await.unwrap();
+
+        let stats = waitlist.stats().await.unwrap();
+        assert_eq!(stats.total_entries, 2);
+        assert_eq!((stats.pending, stats.contacted, stats.converted), (1, 1, 0));
+        assert_eq!(stats.by_interest["Art"], 2);
+        assert_eq!(stats.by_interest["RealEstate"], 1);
+        assert_eq!(stats.by_investment_range.len(), 1);
+        assert_eq!(stats.by_investment_range["Range10K50K"], 1);
+    }
+}
@@ -1,10 +1,14 @@
 use axum::{
+    extract::rejection::{JsonRejection, QueryRejection},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
 use thiserror::Error;
+use uuid::Uuid;
+
+use crate::services::blockchain::OperationPending;
 
 #[derive(Error, Debug)]
 pub enum ApiError {
@@ -19,9 +23,21 @@ pub enum ApiError {
     
     #[error("Não autorizado")]
     Unauthorized,
+
+    #[error("Acesso negado: {0}")]
+    Forbidden(String),
+
+    #[error("Conflito: {0}")]
+    Conflict(String),
+
+    #[error("Limite de requisições excedido: {0}")]
+    TooManyRequests(String),
     
     #[error("Erro de blockchain: {0}")]
     Blockchain(String),
+
+    #[error("Transação {tx_hash} enviada e ainda não confirmada; acompanhe em /api/v1/blockchain/operations/{operation_id}")]
+    OperationPending { operation_id: Uuid, tx_hash: String },
     
     #[error("Erro de validação: {0}")]
     Validation(String),
@@ -29,22 +45,79 @@ pub enum ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
+        let (status, error_message) = match &self {
             ApiError::InternalServer => (StatusCode::INTERNAL_SERVER_ERROR, "Erro interno do servidor"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "Requisição inválida"),
             ApiError::NotFound => (StatusCode::NOT_FOUND, "Não encontrado"),
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Não autorizado"),
+            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "Acesso negado"),
+            ApiError::Conflict(_) => (StatusCode::CONFLICT, "Conflito"),
+            ApiError::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, "Limite de requisições excedido"),
             ApiError::Blockchain(_) => (StatusCode::BAD_GATEWAY, "Erro de blockchain"),
+            ApiError::OperationPending { .. } => (StatusCode::ACCEPTED, "Aguardando confirmação"),
             ApiError::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, "Erro de validação"),
         };
 
-        let body = Json(json!({
+        let mut body = json!({
             "success": false,
             "error": error_message,
             "message": self.to_string(),
             "timestamp": chrono::Utc::now().to_rfc3339()
-        }));
+        });
+        if let ApiError::OperationPending { operation_id, tx_hash } = &self {
+            body["operation_id"] = json!(operation_id);
+            body["tx_hash"] = json!(tx_hash);
+        }
+        let body = Json(body);
 
         (status, body).into_response()
     }
 }
+
+impl ApiError {
+    /// Erro de uma chamada ao `BlockchainService`. Operações que esgotaram a
+    /// espera sem recibo viram 202 com o id para acompanhamento.
+    pub fn blockchain(error: Box<dyn std::error::Error>) -> Self {
+        match error.downcast::<OperationPending>() {
+            Ok(pending) => ApiError::OperationPending {
+                operation_id: pending.operation_id,
+                tx_hash: pending.tx_hash,
+            },
+            Err(error) => ApiError::Blockchain(error.to_string()),
+        }
+    }
+}
+
+/// Corpo JSON que não desserializa (p. ex. um valor em wei acima de 256 bits)
+/// vira erro de validação no formato padrão da API.
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::Validation(rejection.body_text())
+    }
+}
+
+/// Parâmetros de consulta com tipo errado (p. ex. `status=Foo`).
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_operations_answer_202_with_the_operation_id() {
+        let operation_id = Uuid::new_v4();
+        let error = ApiError::blockchain(Box::new(OperationPending {
+            operation_id,
+            tx_hash: "0xabc".to_string(),
+        }));
+        assert!(matches!(&error, ApiError::OperationPending { operation_id: id, .. } if *id == operation_id));
+        assert_eq!(error.into_response().status(), StatusCode::ACCEPTED);
+
+        let error = ApiError::blockchain("execution reverted".into());
+        assert!(matches!(&error, ApiError::Blockchain(message) if message == "execution reverted"));
+    }
+}
@@ -1,6 +1,8 @@
-use axum::{response::Json, http::StatusCode};
+use axum::response::Json;
 use serde_json::{json, Value};
 
+use crate::db::pagination::Page;
+
 pub struct ApiResponse;
 
 impl ApiResponse {
@@ -21,38 +23,19 @@ impl ApiResponse {
         }))
     }
 
-    pub fn error(message: &str) -> (StatusCode, Json<Value>) {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "success": false,
-                "error": message,
-                "timestamp": chrono::Utc::now().to_rfc3339()
-            }))
-        )
-    }
-
-    pub fn bad_request(message: &str) -> (StatusCode, Json<Value>) {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Requisição inválida",
-                "message": message,
-                "timestamp": chrono::Utc::now().to_rfc3339()
-            }))
-        )
-    }
-
-    pub fn not_found(message: &str) -> (StatusCode, Json<Value>) {
-        (
-            StatusCode::NOT_FOUND,
-            Json(json!({
-                "success": false,
-                "error": "Não encontrado",
-                "message": message,
-                "timestamp": chrono::Utc::now().to_rfc3339()
-            }))
-        )
+    /// Envelope das listagens paginadas por cursor: para continuar, repita a
+    /// consulta com `cursor=<next_cursor>`.
+    pub fn paginated<T>(data: Value, page: &Page<T>) -> Json<Value> {
+        Json(json!({
+            "success": true,
+            "data": data,
+            "pagination": {
+                "limit": page.limit,
+                "count": page.items.len(),
+                "has_more": page.next_cursor.is_some(),
+                "next_cursor": page.next_cursor
+            },
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }))
     }
 }
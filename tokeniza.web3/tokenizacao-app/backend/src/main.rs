@@ -1,5 +1,6 @@
 use axum::{
-    http::{header::CONTENT_TYPE, Method},
+    extract::State,
+    http::{header::{AUTHORIZATION, CONTENT_TYPE}, Method, StatusCode},
     response::Json,
     routing::get,
     Router,
@@ -8,21 +9,34 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, Level};
-use tracing_subscriber;
 
 mod api;
+mod auth;
 mod config;
+mod db;
 mod handlers;
 mod models;
 mod services;
+mod units;
 
 use config::AppConfig;
+use db::DbPool;
 use services::blockchain::BlockchainService;
+use services::health_monitor::HealthMonitor;
+use services::indexer::ChainIndexer;
+use services::networks::ChainRegistry;
+use services::tx_manager::TxManager;
+use services::tx_tracker::TxTracker;
 
 #[derive(Clone)]
 pub struct AppState {
+    /// Serviço da rede padrão (`DEFAULT_NETWORK`).
     pub blockchain_service: Arc<BlockchainService>,
+    pub networks: Arc<ChainRegistry>,
     pub config: Arc<AppConfig>,
+    pub db: DbPool,
+    pub api_key_limiter: Arc<auth::rate_limit::KeyRateLimiter>,
+    pub storage: Arc<dyn services::storage::DocumentStorage>,
 }
 
 #[tokio::main]
@@ -37,24 +51,88 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let config = Arc::new(AppConfig::new()?);
     
-    // Initialize blockchain service
-    let blockchain_service = Arc::new(BlockchainService::new(&config).await?);
+    // Initialize database
+    let db = db::connect(&config.database).await?;
+    db::run_migrations(&db).await?;
+    let backfilled = db::AssetRepository::new(db.clone()).backfill_search_columns().await?;
+    if backfilled > 0 {
+        info!("📍 Colunas de busca preenchidas em {} ativos", backfilled);
+    }
+    info!(
+        "🗄️ Banco de dados pronto: {} (schema v{})",
+        db::redacted_url(&config.database.url),
+        db::schema_version(&db).await?.unwrap_or_default()
+    );
+    auth::grant_configured_admins(&db, &config.auth.admin_addresses).await?;
+
+    // Initialize blockchain services, one per configured network
+    let networks = Arc::new(ChainRegistry::new(&config.blockchain, db.clone()).await?);
+    let blockchain_service = networks.default_service();
+
+    HealthMonitor::new(
+        networks.clone(),
+        std::time::Duration::from_secs(config.blockchain.health_check_interval_secs),
+    )
+    .spawn();
+
+    let legacy = db::assign_legacy_chain(&db, blockchain_service.chain_id()).await?;
+    if legacy > 0 {
+        info!(
+            "🔗 {} registros anteriores à migração 0019 atribuídos à rede {}",
+            legacy,
+            blockchain_service.network().name
+        );
+    }
+
+    let storage = services::storage::from_config(&config.storage.backend);
+    info!("📁 Armazenamento de documentos: {}", storage.name());
+
+    // Cada rede tem a própria carteira de operação, nonces e contratos:
+    // rastreamento, reenvio e indexação rodam separadamente em todas.
+    for service in networks.all() {
+        TxTracker::new(
+            db.clone(),
+            service.clone(),
+            config.blockchain.confirmations,
+            std::time::Duration::from_secs(config.blockchain.tx_poll_interval_secs),
+            std::time::Duration::from_secs(config.blockchain.tx_drop_after_secs),
+        )
+        .spawn();
+
+        TxManager::new(
+            db.clone(),
+            service.clone(),
+            config.blockchain.stuck_after_blocks,
+            std::time::Duration::from_secs(config.blockchain.tx_poll_interval_secs),
+        )
+        .spawn();
+
+        if config.indexer.enabled {
+            ChainIndexer::new(db.clone(), service.clone(), storage.clone(), &config.indexer).spawn();
+        }
+    }
 
     let app_state = AppState {
         blockchain_service,
+        networks,
         config: config.clone(),
+        db,
+        api_key_limiter: Arc::default(),
+        storage,
     };
 
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([CONTENT_TYPE]);
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([CONTENT_TYPE, AUTHORIZATION, auth::API_KEY_HEADER]);
 
     // Build router
     let app = Router::new()
         .route("/", get(health_check))
         .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
         .nest("/api/v1", api::routes())
         .layer(cors)
         .with_state(app_state);
@@ -70,11 +148,65 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-async fn health_check() -> Json<Value> {
+async fn health_check(State(state): State<AppState>) -> Json<Value> {
+    let schema_version = db::schema_version(&state.db).await.ok().flatten();
+    let database_ok = db::ping(&state.db).await.is_ok();
+    let networks: Vec<Value> = state
+        .networks
+        .all()
+        .iter()
+        .map(|service| {
+            json!({
+                "network": service.network().name,
+                "chain_id": service.chain_id(),
+                "connected": service.is_connected(),
+                "health": service.health()
+            })
+        })
+        .collect();
+    let ready = database_ok && state.blockchain_service.is_connected();
+
     Json(json!({
-        "status": "ok",
+        "status": if ready { "ok" } else { "degraded" },
         "service": "Tokenização Backend",
         "version": "0.1.0",
+        "database": {
+            "backend": db::DatabaseBackend::from_url(&state.config.database.url).map(|b| b.name()),
+            "reachable": database_ok,
+            "schema_version": schema_version,
+            "latest_schema_version": db::latest_schema_version()
+        },
+        "blockchain": networks,
+        "storage": state.storage.name(),
         "timestamp": chrono::Utc::now().to_rfc3339()
     }))
 }
+
+/// O processo está de pé; não depende de banco nem de blockchain.
+async fn liveness() -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }))
+}
+
+/// Pronto para receber tráfego: banco acessível e rede padrão respondendo.
+async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let database_ok = db::ping(&state.db).await.is_ok();
+    let chain_ok = state.blockchain_service.is_connected();
+    let ready = database_ok && chain_ok;
+
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (
+        status,
+        Json(json!({
+            "status": if ready { "ready" } else { "unavailable" },
+            "checks": {
+                "database": database_ok,
+                "blockchain": chain_ok
+            },
+            "blockchain": state.blockchain_service.health(),
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+    )
+}
@@ -2,21 +2,29 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::units::Wei;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asset {
     pub id: Uuid,
     pub name: String,
     pub description: String,
     pub asset_type: AssetType,
-    pub value: u64, // in wei
+    pub value: Wei,
     pub total_supply: u64,
     pub available_supply: u64,
     pub token_address: Option<String>,
+    pub token_id: Option<String>,
     pub owner: String,
     pub metadata: AssetMetadata,
     pub status: AssetStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Exclusão lógica feita pela API; o ativo some das buscas e consultas.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Rede em que o NFT foi emitido; `None` antes da tokenização.
+    #[serde(default)]
+    pub chain_id: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,41 +37,200 @@ pub enum AssetType {
     Other(String),
 }
 
+impl std::fmt::Display for AssetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetType::RealEstate => write!(f, "RealEstate"),
+            AssetType::Art => write!(f, "Art"),
+            AssetType::Commodity => write!(f, "Commodity"),
+            AssetType::Stock => write!(f, "Stock"),
+            AssetType::Bond => write!(f, "Bond"),
+            AssetType::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl AssetType {
+    /// Inverso do `Display`: nomes desconhecidos viram `Other`.
+    pub fn from_label(label: &str) -> Self {
+        match label {
+            "RealEstate" => AssetType::RealEstate,
+            "Art" => AssetType::Art,
+            "Commodity" => AssetType::Commodity,
+            "Stock" => AssetType::Stock,
+            "Bond" => AssetType::Bond,
+            other => AssetType::Other(other.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetMetadata {
     pub location: Option<String>,
     pub valuation_date: DateTime<Utc>,
     pub appraiser: Option<String>,
+    /// Links informados pelo cliente, mantidos por compatibilidade. Arquivos
+    /// enviados ao backend ficam em `/assets/:id/documents` (`AssetDocument`).
+    #[serde(default)]
     pub documents: Vec<String>,
+    #[serde(default)]
     pub images: Vec<String>,
     pub additional_info: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Ciclo de vida do ativo. Pela API só valem as transições de
+/// `can_transition_to`; o indexador e o TxTracker gravam o que aconteceu
+/// on-chain sem essa checagem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AssetStatus {
     Draft,
     PendingApproval,
     Approved,
+    /// Mint em andamento; reservado pela requisição que o enviou.
+    Tokenizing,
     Tokenized,
     Trading,
     Sold,
     Retired,
 }
 
+impl AssetStatus {
+    pub fn can_transition_to(self, next: AssetStatus) -> bool {
+        use AssetStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, PendingApproval)
+                | (PendingApproval, Approved)
+                | (PendingApproval, Draft)
+                | (Approved, Tokenizing)
+                | (Tokenizing, Tokenized)
+                | (Tokenizing, Approved)
+                | (Tokenized, Trading)
+                | (Trading, Tokenized)
+                | (Trading, Sold)
+                | (Draft | PendingApproval | Approved | Tokenized, Retired)
+        )
+    }
+}
+
+/// Uma linha do histórico de status de um ativo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetStatusChange {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    /// `None` na criação do ativo.
+    pub from_status: Option<AssetStatus>,
+    pub to_status: AssetStatus,
+    /// Endereço de quem fez a mudança, ou `indexer`/`tx_tracker` para
+    /// mudanças vindas da rede.
+    pub changed_by: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AssetStatusChange {
+    pub fn new(
+        asset_id: Uuid,
+        from_status: Option<AssetStatus>,
+        to_status: AssetStatus,
+        changed_by: impl Into<String>,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            asset_id,
+            from_status,
+            to_status,
+            changed_by: changed_by.into(),
+            reason,
+            created_at: crate::db::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetTransitionRequest {
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateAssetRequest {
     pub name: String,
     pub description: String,
     pub asset_type: AssetType,
-    pub value: u64,
+    pub value: Wei,
     pub total_supply: u64,
     pub metadata: AssetMetadata,
 }
 
+/// Campos editáveis enquanto o ativo está em `Draft` ou `PendingApproval`.
+/// `updated_at` é o valor lido pelo cliente: se o ativo mudou desde então, a
+/// edição é recusada com 409.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateAssetRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub metadata: Option<AssetMetadata>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Parâmetros de `DELETE /assets/:id`, com o mesmo papel de `updated_at` da
+/// edição.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeleteAssetQuery {
+    pub updated_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenizeAssetRequest {
     pub asset_id: Uuid,
     pub symbol: String,
     pub decimals: u8,
     pub total_supply: u64,
+    /// Rede do mint (nome ou chain id); sem ela vale a rede padrão.
+    #[serde(default)]
+    pub chain: Option<String>,
+}
+
+/// Parâmetros de `GET /assets`. Filtros ausentes não restringem nada.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssetQuery {
+    /// Rótulo do tipo, como no `Display` de `AssetType`.
+    pub asset_type: Option<String>,
+    pub status: Option<AssetStatus>,
+    pub owner: Option<String>,
+    pub min_value: Option<Wei>,
+    pub max_value: Option<Wei>,
+    /// Trecho de `metadata.location`, sem diferenciar maiúsculas.
+    pub location: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    /// Busca livre em nome e descrição.
+    pub q: Option<String>,
+    #[serde(default)]
+    pub sort: AssetSort,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    Value,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
 }
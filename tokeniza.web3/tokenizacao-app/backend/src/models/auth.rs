@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sessão aberta por um login SIWE. O token entregue ao cliente não é
+/// gravado, apenas o seu hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub address: String,
+    pub chain_id: u64,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginRequest {
+    /// Mensagem EIP-4361 exatamente como foi assinada.
+    pub message: String,
+    pub signature: String,
+}
+
+/// Papéis de acesso, gravados por endereço em `user_roles`. Toda carteira
+/// que entra recebe `Investor`; `Admin` passa em qualquer verificação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    Admin,
+    Appraiser,
+    Issuer,
+    Investor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleGrant {
+    pub address: String,
+    pub role: Role,
+    /// Endereço do admin que concedeu, ou `None` para concessões automáticas.
+    pub granted_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantRoleRequest {
+    pub address: String,
+    pub role: Role,
+}
+
+/// Chave de API para integrações servidor a servidor. Age em nome de
+/// `address` com os papéis de `scopes`; como nas sessões, apenas o hash da
+/// chave é gravado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// Início da chave, para identificá-la em listagens e logs.
+    pub prefix: String,
+    pub address: String,
+    pub scopes: Vec<Role>,
+    pub rate_limit_per_minute: u32,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    /// Carteira registrada como proprietária/vendedora nas operações da chave.
+    pub address: String,
+    pub scopes: Vec<Role>,
+    /// Sem valor, vale `API_KEY_RATE_LIMIT_PER_MINUTE`.
+    pub rate_limit_per_minute: Option<u32>,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Evento de contrato lido da blockchain pelo indexador.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainEvent {
+    pub id: Uuid,
+    pub block_number: u64,
+    pub tx_hash: String,
+    pub log_index: u64,
+    pub contract: String,
+    pub event: String,
+    pub data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Linha de bloco já processada pelo indexador, usada para detectar reorgs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedBlock {
+    pub block_number: u64,
+    pub block_hash: String,
+    pub parent_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EffectEntity {
+    Asset,
+    Listing,
+    Transaction,
+}
+
+/// Escrita derivada de um evento, com o estado anterior da linha (`None`
+/// quando o evento a criou) para que possa ser desfeita após um reorg.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainEffect {
+    pub id: Uuid,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub step: u64,
+    pub entity: EffectEntity,
+    pub entity_id: Uuid,
+    pub previous: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Tipo de um arquivo anexado ao ativo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DocumentKind {
+    /// Escritura ou título de propriedade.
+    Deed,
+    /// Laudo de avaliação.
+    Appraisal,
+    /// Apólice de seguro.
+    Insurance,
+    /// Foto do ativo.
+    Image,
+    Other,
+}
+
+/// Arquivo enviado para um ativo. O conteúdo fica no armazenamento
+/// configurado (`STORAGE_BACKEND`), sob `storage_key`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetDocument {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub kind: DocumentKind,
+    /// Nome original do arquivo, apenas para exibição e download.
+    pub file_name: String,
+    pub content_type: String,
+    pub size: u64,
+    /// SHA-256 do conteúdo, em hexadecimal.
+    pub sha256: String,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -2,14 +2,17 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::units::Wei;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Listing {
     pub id: Uuid,
     pub asset_id: Uuid,
     pub seller: String,
-    pub price: u64, // in wei
+    pub price: Wei,
     pub quantity: u64,
     pub status: ListingStatus,
+    pub tx_hash: Option<String>,
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
 }
@@ -22,19 +25,50 @@ pub enum ListingStatus {
     Expired,
 }
 
+/// Pede as transações que a carteira do vendedor assina para listar o ativo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepareListingRequest {
+    pub asset_id: Uuid,
+    /// Em wei; alternativamente `price_eth`, em ETH decimal ("1.5").
+    #[serde(default)]
+    pub price: Option<Wei>,
+    #[serde(default)]
+    pub price_eth: Option<String>,
+}
+
+/// Registra a oferta a partir do `listAsset` já minerado; o preço e o
+/// vendedor vêm do evento `AssetListed`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateListingRequest {
     pub asset_id: Uuid,
-    pub price: u64,
+    pub tx_hash: String,
     pub quantity: u64,
     pub expires_at: Option<DateTime<Utc>>,
 }
 
+/// Hash de uma transação enviada pela carteira do usuário.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTxRequest {
+    pub tx_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateFeeRequest {
+    /// Pontos-base: 250 = 2,5%.
+    pub fee_bps: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparePurchaseRequest {
+    pub listing_id: Uuid,
+}
+
+/// Registra a compra enviada pela carteira da sessão; o comprador é sempre
+/// essa carteira e a oferta é comprada inteira.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PurchaseRequest {
     pub listing_id: Uuid,
-    pub quantity: u64,
-    pub buyer: String,
+    pub tx_hash: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,7 +77,7 @@ pub struct Transaction {
     pub listing_id: Uuid,
     pub buyer: String,
     pub seller: String,
-    pub price: u64,
+    pub price: Wei,
     pub quantity: u64,
     pub tx_hash: Option<String>,
     pub status: TransactionStatus,
@@ -1,7 +1,15 @@
 pub mod asset;
+pub mod auth;
+pub mod chain_event;
+pub mod document;
 pub mod marketplace;
+pub mod tx_operation;
 pub mod waitlist;
 
 pub use asset::*;
+pub use auth::*;
+pub use chain_event::*;
+pub use document::*;
 pub use marketplace::*;
+pub use tx_operation::*;
 pub use waitlist::*;
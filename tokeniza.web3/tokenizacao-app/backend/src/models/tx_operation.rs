@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Operação lógica enviada pela carteira de operação. Ocupa um nonce e pode
+/// ter várias tentativas on-chain (reenvios com taxa maior ou cancelamento).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxOperation {
+    pub id: Uuid,
+    pub kind: OperationKind,
+    /// Rede em que o nonce foi consumido.
+    pub chain_id: u64,
+    pub nonce: u64,
+    /// Hash da tentativa minerada, ou da mais recente enquanto pendente.
+    pub tx_hash: String,
+    pub status: OperationStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationKind {
+    MintAsset,
+    VerifyAsset,
+    ApproveListing,
+    ListAsset,
+    BuyAsset,
+    CancelListing,
+    CancelNonce,
+    UpdateMarketplaceFee,
+    WithdrawFees,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationStatus {
+    Pending,
+    Mined,
+    Cancelled,
+    /// O nonce foi consumido por uma transação que não é nenhuma das tentativas.
+    Dropped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxAttempt {
+    pub id: Uuid,
+    pub operation_id: Uuid,
+    pub tx_hash: String,
+    pub reason: AttemptReason,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+    /// Transação assinada (sem assinatura), usada como base dos reenvios.
+    pub payload: serde_json::Value,
+    pub submitted_block: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttemptReason {
+    Initial,
+    GasBump,
+    Cancel,
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaitlistEntry {
@@ -31,6 +32,19 @@ pub enum WaitlistStatus {
     Unsubscribed,
 }
 
+/// Contagens da lista de espera; entradas sem faixa de investimento não
+/// aparecem em `by_investment_range`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WaitlistStats {
+    pub total_entries: u64,
+    pub pending: u64,
+    pub contacted: u64,
+    pub converted: u64,
+    pub unsubscribed: u64,
+    pub by_interest: BTreeMap<String, u64>,
+    pub by_investment_range: BTreeMap<String, u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddToWaitlistRequest {
     pub email: String,
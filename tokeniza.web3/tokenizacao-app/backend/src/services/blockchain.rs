@@ -1,110 +1,1056 @@
 use ethers::{
+    contract::parse_log,
     prelude::*,
-    providers::{Http, Provider},
+    providers::Provider,
     signers::{LocalWallet, Signer},
-    types::{Address, U256},
+    types::{transaction::eip2718::TypedTransaction, Address, H256, U256},
 };
 use std::sync::Arc;
-use tracing::info;
-use crate::config::{AppConfig, BlockchainConfig};
-use crate::models::{Asset, TokenizeAssetRequest};
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::config::{BlockchainConfig, NetworkConfig};
+use crate::db::{DbPool, TxOperationRepository};
+use crate::models::{
+    Asset, AttemptReason, OperationKind, OperationStatus, TxAttempt, TxOperation,
+};
+use crate::services::contracts::{
+    AssetMintedFilter, AssetSoldFilter, AssetToken, Marketplace, MarketplaceEvents, ShareToken,
+};
+use crate::services::nonce_manager::{NonceManager, NonceStats};
+use crate::services::rpc::{EndpointStats, FailoverClient};
+use crate::services::subscriptions::ChainSubscriptions;
+
+type SignerClient = NonceManager<SignerMiddleware<Arc<Provider<FailoverClient>>, LocalWallet>>;
 
 pub struct BlockchainService {
-    provider: Arc<Provider<Http>>,
-    wallet: Option<LocalWallet>,
-    chain_id: u64,
+    provider: Arc<Provider<FailoverClient>>,
+    client: Option<Arc<SignerClient>>,
+    network: NetworkConfig,
     config: BlockchainConfig,
+    operations: TxOperationRepository,
+    health: std::sync::Mutex<ChainHealth>,
+    subscriptions: Option<Arc<ChainSubscriptions>>,
+}
+
+/// Resultado da última verificação do `HealthMonitor` para uma rede.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ChainHealth {
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_success_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub latest_block: Option<u64>,
+    pub latency_ms: Option<u64>,
+    /// Chain id informado pelo nó, quando difere do configurado.
+    pub chain_id_mismatch: Option<u64>,
+    pub signer_balance_wei: Option<String>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Endereços dos contratos da plataforma que estão configurados.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlatformContracts {
+    pub asset_token: Option<Address>,
+    pub marketplace: Option<Address>,
+    pub waitlist: Option<Address>,
+}
+
+/// Nonces da carteira de operação: o próximo que será usado localmente e o
+/// que a rede reporta (`latest` e `pending`).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NonceStatus {
+    pub address: Address,
+    pub latest_nonce: u64,
+    pub pending_nonce: u64,
+    pub in_mempool: u64,
+    #[serde(flatten)]
+    pub manager: NonceStats,
+}
+
+/// Percentis de `eth_feeHistory` usados nas sugestões lenta, padrão e rápida.
+const FEE_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+const FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Gas típico das operações da plataforma; `list` inclui o `approve` que
+/// precede o `listAsset`.
+pub const OPERATION_GAS: [(&str, u64); 3] = [("mint", 250_000), ("list", 200_000), ("buy", 120_000)];
+
+#[derive(Debug, Clone)]
+pub struct MarketplaceFees {
+    pub fee_bps: U256,
+    pub accrued: U256,
+}
+
+/// Preços de gas atuais: o legado (`eth_gasPrice`), a base fee do último
+/// bloco e as gorjetas sugeridas a partir do histórico recente.
+#[derive(Debug, Clone)]
+pub struct FeeEstimate {
+    pub block_number: u64,
+    pub gas_price: U256,
+    /// `None` em redes sem EIP-1559.
+    pub base_fee_per_gas: Option<U256>,
+    /// Lenta, padrão e rápida.
+    pub priority_fees: [U256; 3],
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum TokenStandard {
+    #[serde(rename = "ERC721")]
+    Erc721,
+    #[serde(rename = "ERC20")]
+    Erc20,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBalance {
+    pub standard: TokenStandard,
+    pub balance: U256,
+    pub decimals: u8,
+}
+
+/// Resultado de um `mintAsset` minerado com sucesso.
+#[derive(Debug, Clone)]
+pub struct MintedAsset {
+    pub contract_address: Address,
+    pub token_id: U256,
+    pub tx_hash: H256,
+}
+
+/// Transação que a carteira do usuário assina e envia. O backend só monta a
+/// chamada: não paga gas nem custodia o NFT. Os campos seguem o
+/// `eth_sendTransaction`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PreparedTx {
+    /// Método do contrato, para a interface identificar o passo.
+    pub method: &'static str,
+    pub chain_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub value: U256,
+}
+
+/// A operação foi enviada, mas não foi minerada dentro do prazo de espera.
+/// Ela continua registrada e o `TxManager` segue acompanhando.
+#[derive(Debug, Clone)]
+pub struct OperationPending {
+    pub operation_id: Uuid,
+    pub tx_hash: String,
+}
+
+impl std::fmt::Display for OperationPending {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Operation {} ({}) is still pending", self.operation_id, self.tx_hash)
+    }
+}
+
+impl std::error::Error for OperationPending {}
+
 impl BlockchainService {
-    pub async fn new(config: &AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
-        let provider = Provider::<Http>::try_from(&config.blockchain.rpc_url)?;
-        let provider = Arc::new(provider);
+    pub async fn new(
+        network: &NetworkConfig,
+        config: &BlockchainConfig,
+        db: DbPool,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let transport = FailoverClient::new(
+            network.request_urls(),
+            config.rpc_max_retries,
+            std::time::Duration::from_millis(config.rpc_backoff_ms),
+            config.ws_reconnects,
+        )?;
+        let provider = Arc::new(Provider::new(transport));
 
-        let wallet = if let Some(private_key) = &config.blockchain.private_key {
-            Some(private_key.parse::<LocalWallet>()?.with_chain_id(config.blockchain.chain_id))
+        let client = if let Some(private_key) = &network.private_key {
+            let wallet = private_key
+                .parse::<LocalWallet>()?
+                .with_chain_id(network.chain_id);
+            let address = wallet.address();
+            info!("🔑 Carteira de operação em {}: {:?}", network.name, address);
+            let signer = SignerMiddleware::new(provider.clone(), wallet);
+            Some(Arc::new(NonceManager::new(signer, address)))
         } else {
             None
         };
 
-        info!("🔗 Conectado à blockchain - Chain ID: {}", config.blockchain.chain_id);
+        info!("🔗 Rede {} configurada - Chain ID: {}", network.name, network.chain_id);
 
-        Ok(Self {
+        let mut service = Self {
             provider,
-            wallet,
-            chain_id: config.blockchain.chain_id,
-            config: config.blockchain.clone(),
-        })
+            client,
+            network: network.clone(),
+            config: config.clone(),
+            operations: TxOperationRepository::new(db),
+            health: std::sync::Mutex::default(),
+            subscriptions: None,
+        };
+
+        if !network.ws_urls.is_empty() {
+            let contracts = service.platform_contracts();
+            let addresses = [contracts.asset_token, contracts.marketplace, contracts.waitlist]
+                .into_iter()
+                .flatten()
+                .collect();
+            let subscriptions =
+                ChainSubscriptions::new(network.ws_urls.clone(), addresses, config.ws_reconnects);
+            subscriptions.spawn();
+            service.subscriptions = Some(subscriptions);
+        }
+
+        Ok(service)
+    }
+
+    /// `None` quando a rede não tem endpoint WebSocket configurado.
+    pub fn subscriptions(&self) -> Option<Arc<ChainSubscriptions>> {
+        self.subscriptions.clone()
+    }
+
+    pub fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.network.chain_id
+    }
+
+    /// Uso de cada endpoint RPC da rede.
+    pub fn rpc_stats(&self) -> Vec<EndpointStats> {
+        self.transport().stats()
+    }
+
+    fn transport(&self) -> &FailoverClient {
+        (*self.provider).as_ref()
+    }
+
+    /// Leituras críticas passam pelo quórum quando `RPC_QUORUM` > 1.
+    async fn critical_read<R>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<R, Box<dyn std::error::Error>>
+    where
+        R: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send,
+    {
+        if self.network.rpc_quorum > 1 {
+            Ok(self
+                .transport()
+                .request_quorum(method, params, self.network.rpc_quorum)
+                .await?)
+        } else {
+            Ok(self.provider.request(method, params).await?)
+        }
+    }
+
+    /// Leitura de estado crítica; o bloco é o último parâmetro. Com quórum,
+    /// todos os endpoints são consultados no mesmo bloco.
+    async fn critical_state_read<R>(
+        &self,
+        method: &str,
+        mut params: Vec<serde_json::Value>,
+    ) -> Result<R, Box<dyn std::error::Error>>
+    where
+        R: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send,
+    {
+        let block = if self.network.rpc_quorum > 1 {
+            let block = self.transport().quorum_block(self.network.rpc_quorum).await?;
+            format!("{:#x}", block)
+        } else {
+            "latest".to_string()
+        };
+        params.push(serde_json::Value::String(block));
+
+        self.critical_read(method, serde_json::Value::Array(params)).await
     }
 
     pub async fn get_balance(&self, address: &str) -> Result<U256, Box<dyn std::error::Error>> {
         let address: Address = address.parse()?;
-        let balance = self.provider.get_balance(address, None).await?;
-        Ok(balance)
-    }    pub async fn get_block_number(&self) -> Result<U256, Box<dyn std::error::Error>> {
+        self.critical_state_read("eth_getBalance", vec![serde_json::json!(address)])
+            .await
+    }
+
+    pub async fn get_block_number(&self) -> Result<U256, Box<dyn std::error::Error>> {
         let block_number = self.provider.get_block_number().await?;
         Ok(U256::from(block_number.as_u64()))
-    }    pub async fn deploy_asset_token(
+    }
+
+    fn signer(&self) -> Result<Arc<SignerClient>, Box<dyn std::error::Error>> {
+        self.client.clone().ok_or_else(|| "Wallet not configured".into())
+    }
+
+    fn asset_token_address(&self) -> Result<Address, Box<dyn std::error::Error>> {
+        let address = self
+            .network
+            .contract_addresses
+            .asset_token
+            .as_deref()
+            .ok_or("ASSET_TOKEN_ADDRESS not configured")?;
+        Ok(address.parse()?)
+    }
+
+    fn marketplace_address(&self) -> Result<Address, Box<dyn std::error::Error>> {
+        let address = self
+            .network
+            .contract_addresses
+            .marketplace
+            .as_deref()
+            .ok_or("MARKETPLACE_ADDRESS not configured")?;
+        Ok(address.parse()?)
+    }
+
+    pub fn platform_contracts(&self) -> PlatformContracts {
+        let parse = |address: &Option<String>| address.as_deref().and_then(|a| a.parse().ok());
+        let addresses = &self.network.contract_addresses;
+
+        PlatformContracts {
+            asset_token: parse(&addresses.asset_token),
+            marketplace: parse(&addresses.marketplace),
+            waitlist: parse(&addresses.waitlist),
+        }
+    }
+
+    pub async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Box<dyn std::error::Error>> {
+        Ok(self.provider.get_logs(filter).await?)
+    }
+
+    /// `None` quando não há carteira de operação configurada.
+    pub async fn nonce_status(&self) -> Result<Option<NonceStatus>, Box<dyn std::error::Error>> {
+        let Some(client) = &self.client else {
+            return Ok(None);
+        };
+
+        let address = client.address();
+        let latest = self
+            .provider
+            .get_transaction_count(address, Some(BlockNumber::Latest.into()))
+            .await?
+            .as_u64();
+        let pending = self
+            .provider
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?
+            .as_u64();
+
+        Ok(Some(NonceStatus {
+            address,
+            latest_nonce: latest,
+            pending_nonce: pending,
+            in_mempool: pending.saturating_sub(latest),
+            manager: client.stats(),
+        }))
+    }
+
+    pub async fn fee_estimate(&self) -> Result<FeeEstimate, Box<dyn std::error::Error>> {
+        let gas_price = self.provider.get_gas_price().await?;
+        let block = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or("Latest block not available")?;
+        let block_number = block.number.unwrap_or_default().as_u64();
+
+        let Some(base_fee_per_gas) = block.base_fee_per_gas else {
+            return Ok(FeeEstimate {
+                block_number,
+                gas_price,
+                base_fee_per_gas: None,
+                priority_fees: [U256::zero(); 3],
+            });
+        };
+
+        let history = self
+            .provider
+            .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &FEE_PERCENTILES)
+            .await?;
+
+        // Mediana de cada percentil entre os blocos; blocos vazios reportam
+        // gorjeta zero e são ignorados.
+        let mut priority_fees = [U256::from(ethers::utils::EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE); 3];
+        for (i, fee) in priority_fees.iter_mut().enumerate() {
+            let mut rewards: Vec<U256> = history
+                .reward
+                .iter()
+                .filter_map(|block| block.get(i).copied())
+                .filter(|reward| !reward.is_zero())
+                .collect();
+            if !rewards.is_empty() {
+                rewards.sort();
+                *fee = rewards[rewards.len() / 2];
+            }
+        }
+        // Percentis maiores nunca sugerem uma gorjeta menor.
+        priority_fees[1] = priority_fees[1].max(priority_fees[0]);
+        priority_fees[2] = priority_fees[2].max(priority_fees[1]);
+
+        Ok(FeeEstimate {
+            block_number,
+            gas_price,
+            base_fee_per_gas: Some(base_fee_per_gas),
+            priority_fees,
+        })
+    }
+
+    pub async fn get_block(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<Block<H256>>, Box<dyn std::error::Error>> {
+        Ok(self.provider.get_block(block_number).await?)
+    }
+
+    pub async fn token_uri(&self, token_id: U256) -> Result<String, Box<dyn std::error::Error>> {
+        let contract = AssetToken::new(self.asset_token_address()?, self.provider.clone());
+        Ok(contract.token_uri(token_id).call().await?)
+    }
+
+    /// Emite o NFT do ativo via `AssetToken.mintAsset` e aguarda a mineração
+    /// para extrair o `tokenId` do evento `AssetMinted`.
+    pub async fn mint_asset(
         &self,
-        _request: &TokenizeAssetRequest,
         asset: &Asset,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        if self.wallet.is_none() {
-            return Err("Wallet not configured".into());
+        token_uri: &str,
+    ) -> Result<MintedAsset, Box<dyn std::error::Error>> {
+        let client = self.signer()?;
+        let contract_address = self.asset_token_address()?;
+        let contract = AssetToken::new(contract_address, client);
+
+        let to: Address = asset.owner.parse()?;
+        let location = asset.metadata.location.clone().unwrap_or_default();
+
+        info!("🚀 Minting asset token for: {}", asset.name);
+
+        let call = contract.mint_asset(
+            to,
+            token_uri.to_string(),
+            asset.asset_type.to_string(),
+            asset.value.into(),
+            location,
+        );
+        let operation = self.submit(OperationKind::MintAsset, call).await?;
+        let receipt = self.wait_for_operation(&operation).await?;
+        ensure_success(&receipt, "mintAsset")?;
+        let tx_hash = receipt.transaction_hash;
+
+        let token_id = receipt
+            .logs
+            .into_iter()
+            .filter(|log| log.address == contract_address)
+            .find_map(|log| parse_log::<AssetMintedFilter>(log).ok())
+            .map(|event| event.token_id)
+            .ok_or("AssetMinted event not found in receipt")?;
+
+        info!("✅ Asset token minted - Token ID: {} TX: {:?}", token_id, tx_hash);
+
+        Ok(MintedAsset {
+            contract_address,
+            token_id,
+            tx_hash,
+        })
+    }
+
+    /// Marca o ativo como verificado on-chain via `AssetToken.verifyAsset`.
+    pub async fn verify_asset(&self, token_id: U256) -> Result<H256, Box<dyn std::error::Error>> {
+        let contract = AssetToken::new(self.asset_token_address()?, self.signer()?);
+
+        let call = contract.verify_asset(token_id);
+        let operation = self.submit(OperationKind::VerifyAsset, call).await?;
+        let receipt = self.wait_for_operation(&operation).await?;
+        ensure_success(&receipt, "verifyAsset")?;
+
+        info!("✅ Asset {} verified - TX: {:?}", token_id, receipt.transaction_hash);
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Dono atual do NFT segundo `ownerOf`.
+    pub async fn nft_owner(
+        &self,
+        nft_contract: Address,
+        token_id: U256,
+    ) -> Result<Address, Box<dyn std::error::Error>> {
+        let asset_token = AssetToken::new(nft_contract, self.provider.clone());
+        Ok(asset_token.owner_of(token_id).call().await?)
+    }
+
+    /// Monta o `approve` (se o Marketplace ainda não puder mover o token) e o
+    /// `listAsset` que a carteira dona do NFT assina. Listado, o NFT fica em
+    /// custódia no Marketplace até a venda ou o cancelamento.
+    pub async fn prepare_listing(
+        &self,
+        owner: Address,
+        nft_contract: Address,
+        token_id: U256,
+        price: U256,
+    ) -> Result<Vec<PreparedTx>, Box<dyn std::error::Error>> {
+        let marketplace_address = self.marketplace_address()?;
+        let asset_token = AssetToken::new(nft_contract, self.provider.clone());
+        let marketplace = Marketplace::new(marketplace_address, self.provider.clone());
+
+        let mut transactions = Vec::new();
+        let approved = asset_token.get_approved(token_id).call().await? == marketplace_address
+            || asset_token
+                .is_approved_for_all(owner, marketplace_address)
+                .call()
+                .await?;
+        if !approved {
+            let approve = asset_token.approve(marketplace_address, token_id);
+            transactions.push(self.prepared("approve", owner, &approve.tx));
+        }
+
+        let call = marketplace.list_asset(nft_contract, token_id, price);
+        transactions.push(self.prepared("listAsset", owner, &call.tx));
+        Ok(transactions)
+    }
+
+    /// Monta o `Marketplace.buyAsset` que a carteira do comprador assina,
+    /// pagando `price` como `msg.value`.
+    pub fn prepare_purchase(
+        &self,
+        buyer: Address,
+        nft_contract: Address,
+        token_id: U256,
+        price: U256,
+    ) -> Result<PreparedTx, Box<dyn std::error::Error>> {
+        let marketplace = Marketplace::new(self.marketplace_address()?, self.provider.clone());
+
+        let call = marketplace.buy_asset(nft_contract, token_id).value(price);
+        Ok(self.prepared("buyAsset", buyer, &call.tx))
+    }
+
+    /// Confere se a transação é um `buyAsset` deste token enviado ao
+    /// Marketplace, e devolve o valor pago.
+    pub fn purchase_payment(
+        &self,
+        tx: &Transaction,
+        nft_contract: Address,
+        token_id: U256,
+    ) -> Result<Option<U256>, Box<dyn std::error::Error>> {
+        let marketplace = Marketplace::new(self.marketplace_address()?, self.provider.clone());
+        let expected = marketplace.buy_asset(nft_contract, token_id).calldata();
+
+        let matches = tx.to == Some(marketplace.address()) && expected.as_ref() == Some(&tx.input);
+        Ok(matches.then_some(tx.value))
+    }
+
+    /// Comprador registrado no `AssetSold` deste token dentro do recibo.
+    pub fn purchase_buyer(
+        &self,
+        receipt: &TransactionReceipt,
+        nft_contract: Address,
+        token_id: U256,
+    ) -> Result<Option<Address>, Box<dyn std::error::Error>> {
+        let marketplace_address = self.marketplace_address()?;
+
+        Ok(receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == marketplace_address)
+            .filter_map(|log| parse_log::<AssetSoldFilter>(log.clone()).ok())
+            .find(|event| event.nft_contract == nft_contract && event.token_id == token_id)
+            .map(|event| event.buyer))
+    }
+
+    /// Envia a chamada pela fila de nonces e registra a operação com sua
+    /// primeira tentativa, para que o `TxManager` possa reenviá-la.
+    async fn submit<D>(
+        &self,
+        kind: OperationKind,
+        call: ContractCall<SignerClient, D>,
+    ) -> Result<TxOperation, Box<dyn std::error::Error>> {
+        let client = self.signer()?;
+        let (pending, tx) = client.submit(call.tx, call.block).await?;
+        let tx_hash = pending.tx_hash();
+
+        let now = crate::db::now();
+        let operation = TxOperation {
+            id: Uuid::new_v4(),
+            kind,
+            chain_id: self.chain_id(),
+            nonce: tx.nonce().copied().unwrap_or_default().as_u64(),
+            tx_hash: format!("{:?}", tx_hash),
+            status: OperationStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        };
+        self.operations.create(&operation).await?;
+        self.record_attempt(operation.id, AttemptReason::Initial, &tx, tx_hash)
+            .await?;
+
+        Ok(operation)
+    }
+
+    async fn record_attempt(
+        &self,
+        operation_id: Uuid,
+        reason: AttemptReason,
+        tx: &TypedTransaction,
+        tx_hash: H256,
+    ) -> Result<TxAttempt, Box<dyn std::error::Error>> {
+        let (max_fee, priority_fee) = match tx {
+            TypedTransaction::Eip1559(inner) => (inner.max_fee_per_gas, inner.max_priority_fee_per_gas),
+            other => (other.gas_price(), None),
+        };
+
+        let attempt = TxAttempt {
+            id: Uuid::new_v4(),
+            operation_id,
+            tx_hash: format!("{:?}", tx_hash),
+            reason,
+            max_fee_per_gas: max_fee.map(|fee| fee.to_string()),
+            max_priority_fee_per_gas: priority_fee.map(|fee| fee.to_string()),
+            payload: serde_json::to_value(tx)?,
+            submitted_block: self.provider.get_block_number().await?.as_u64(),
+            created_at: crate::db::now(),
+        };
+        self.operations.add_attempt(&attempt).await?;
+
+        Ok(attempt)
+    }
+
+    /// Aguarda até que alguma tentativa da operação seja minerada, seja a
+    /// original ou um reenvio feito pelo `TxManager`. Passado
+    /// `tx_wait_timeout_secs`, devolve `OperationPending`; falhas do nó até
+    /// lá só adiam a consulta.
+    async fn wait_for_operation(
+        &self,
+        operation: &TxOperation,
+    ) -> Result<TransactionReceipt, Box<dyn std::error::Error>> {
+        let deadline = tokio::time::Instant::now()
+            + std::time::Duration::from_secs(self.config.tx_wait_timeout_secs);
+
+        loop {
+            let attempts = self.operations.attempts(operation.id).await?;
+            let mined = self.mined_attempt(&attempts).await.map_err(|e| e.to_string());
+            match mined {
+                Ok(Some((attempt, receipt))) => {
+                    self.complete_operation(operation.clone(), &attempt).await?;
+                    if attempt.reason == AttemptReason::Cancel {
+                        return Err(format!("Operation {} (nonce {}) was cancelled", operation.id, operation.nonce).into());
+                    }
+                    return Ok(receipt);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("⚠️ Could not check operation {}: {}", operation.id, e),
+            }
+
+            let current = self.operations.find_by_id(operation.id).await?;
+            if current.is_some_and(|op| op.status == OperationStatus::Dropped) {
+                return Err(format!("Nonce {} was used by another transaction", operation.nonce).into());
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(OperationPending {
+                    operation_id: operation.id,
+                    tx_hash: operation.tx_hash.clone(),
+                }
+                .into());
+            }
+            tokio::time::sleep(self.provider.get_interval()).await;
+        }
+    }
+
+    /// Tentativa que já tem recibo, se houver.
+    pub async fn mined_attempt(
+        &self,
+        attempts: &[TxAttempt],
+    ) -> Result<Option<(TxAttempt, TransactionReceipt)>, Box<dyn std::error::Error>> {
+        for attempt in attempts {
+            let receipt = self
+                .provider
+                .get_transaction_receipt(attempt.tx_hash.parse::<H256>()?)
+                .await?;
+            if let Some(receipt) = receipt {
+                return Ok(Some((attempt.clone(), receipt)));
+            }
         }
+        Ok(None)
+    }
+
+    pub async fn complete_operation(
+        &self,
+        mut operation: TxOperation,
+        mined: &TxAttempt,
+    ) -> Result<TxOperation, Box<dyn std::error::Error>> {
+        operation.status = match mined.reason {
+            AttemptReason::Cancel => OperationStatus::Cancelled,
+            _ => OperationStatus::Mined,
+        };
+        operation.tx_hash = mined.tx_hash.clone();
+        operation.updated_at = crate::db::now();
+        self.operations.update(&operation).await?;
+        Ok(operation)
+    }
+
+    /// Nonce da próxima transação minerada da carteira de operação; nonces
+    /// abaixo dele já foram consumidos.
+    pub async fn confirmed_nonce(&self) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        let Some(client) = &self.client else {
+            return Ok(None);
+        };
+        let nonce = self
+            .provider
+            .get_transaction_count(client.address(), Some(BlockNumber::Latest.into()))
+            .await?;
+        Ok(Some(nonce.as_u64()))
+    }
+
+    /// Reenvia a última tentativa da operação com o mesmo nonce e taxas
+    /// maiores. Com `AttemptReason::Cancel` a chamada original é trocada por
+    /// uma transferência de valor zero para a própria carteira.
+    pub async fn replace_operation(
+        &self,
+        operation: &TxOperation,
+        reason: AttemptReason,
+    ) -> Result<TxAttempt, Box<dyn std::error::Error>> {
+        let client = self.signer()?;
+        let attempts = self.operations.attempts(operation.id).await?;
+        let last = attempts.last().ok_or("Operation has no attempts")?;
+        let previous: TypedTransaction = serde_json::from_value(last.payload.clone())?;
+
+        let mut tx = match reason {
+            AttemptReason::Cancel => self.self_transfer(client.address(), &previous),
+            _ => previous.clone(),
+        };
+        self.bump_fees(&previous, &mut tx).await?;
+
+        // Nonce explícito: o NonceManager repassa sem consumir a fila.
+        let tx_hash = client.send_transaction(tx.clone(), None).await?.tx_hash();
+        let attempt = self.record_attempt(operation.id, reason, &tx, tx_hash).await?;
+
+        let mut operation = operation.clone();
+        operation.tx_hash = attempt.tx_hash.clone();
+        operation.updated_at = crate::db::now();
+        self.operations.update(&operation).await?;
 
-        // Aqui você implementaria o deploy do contrato usando ethers-rs
-        // Por enquanto, vamos simular o deploy
-        info!("🚀 Deploying asset token for: {}", asset.name);
-        
-        // Simulated contract address
-        let contract_address = format!("0x{:040x}", rand::random::<u64>());
-        
-        info!("✅ Asset token deployed at: {}", contract_address);
-        Ok(contract_address)
-    }    pub async fn mint_tokens(
-        &self,
-        _contract_address: &str,
-        to: &str,
-        amount: U256,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        if self.wallet.is_none() {
-            return Err("Wallet not configured".into());
+        info!(
+            "⛽ Nonce {} reenviado ({:?}) - TX: {:?} (max fee {:?})",
+            operation.nonce, reason, tx_hash, attempt.max_fee_per_gas
+        );
+        Ok(attempt)
+    }
+
+    /// Cancela um nonce pendente da carteira de operação. Se ele pertence a uma
+    /// operação registrada, o cancelamento entra como nova tentativa dela.
+    pub async fn cancel_nonce(&self, nonce: u64) -> Result<TxOperation, Box<dyn std::error::Error>> {
+        if let Some(operation) = self.operations.find_pending_by_nonce(self.chain_id(), nonce).await? {
+            self.replace_operation(&operation, AttemptReason::Cancel).await?;
+            return Ok(self
+                .operations
+                .find_by_id(operation.id)
+                .await?
+                .ok_or("Operation disappeared")?);
+        }
+
+        let client = self.signer()?;
+        let address = client.address();
+        let latest = self
+            .provider
+            .get_transaction_count(address, Some(BlockNumber::Latest.into()))
+            .await?
+            .as_u64();
+        let pending = self
+            .provider
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?
+            .as_u64();
+        if nonce < latest || nonce >= pending {
+            return Err(format!("Nonce {} is not pending (pending range {}..{})", nonce, latest, pending).into());
+        }
+
+        // Nonce pendente enviado fora do registro de operações: a base das
+        // taxas é a estimativa atual.
+        let mut base = self.self_transfer(address, &Eip1559TransactionRequest::new().into());
+        base.set_nonce(nonce);
+        let mut tx = base.clone();
+        self.bump_fees(&base, &mut tx).await?;
+
+        let tx_hash = client.send_transaction(tx.clone(), None).await?.tx_hash();
+        let now = crate::db::now();
+        let operation = TxOperation {
+            id: Uuid::new_v4(),
+            kind: OperationKind::CancelNonce,
+            chain_id: self.chain_id(),
+            nonce,
+            tx_hash: format!("{:?}", tx_hash),
+            status: OperationStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        };
+        self.operations.create(&operation).await?;
+        self.record_attempt(operation.id, AttemptReason::Cancel, &tx, tx_hash)
+            .await?;
+
+        Ok(operation)
+    }
+
+    fn self_transfer(&self, address: Address, previous: &TypedTransaction) -> TypedTransaction {
+        let nonce = previous.nonce().copied();
+        let mut tx: TypedTransaction = match previous {
+            TypedTransaction::Eip1559(_) => Eip1559TransactionRequest::new().into(),
+            _ => TransactionRequest::new().into(),
+        };
+        tx.set_from(address);
+        tx.set_to(address);
+        tx.set_value(U256::zero());
+        tx.set_gas(21_000);
+        tx.set_chain_id(self.network.chain_id);
+        if let Some(nonce) = nonce {
+            tx.set_nonce(nonce);
         }
+        tx
+    }
 
-        info!("🪙 Minting {} tokens to {}", amount, to);
-        
-        // Simulated transaction hash
-        let tx_hash = format!("0x{:064x}", rand::random::<u64>());
-        
-        info!("✅ Tokens minted - TX: {}", tx_hash);
-        Ok(tx_hash)
-    }    pub async fn transfer_tokens(
-        &self,
-        _contract_address: &str,
-        from: &str,
-        to: &str,
-        amount: U256,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        info!("🔄 Transferring {} tokens from {} to {}", amount, from, to);
-        
-        // Simulated transaction hash
-        let tx_hash = format!("0x{:064x}", rand::random::<u64>());
-        
-        info!("✅ Transfer completed - TX: {}", tx_hash);
-        Ok(tx_hash)
-    }    pub async fn get_token_balance(
-        &self,
-        _contract_address: &str,
-        _address: &str,
+    /// Taxas `TX_FEE_BUMP_PERCENT`% acima das anteriores (ou a estimativa
+    /// atual, se maior), limitadas a `TX_MAX_FEE_GWEI`.
+    async fn bump_fees(
+        &self,
+        previous: &TypedTransaction,
+        tx: &mut TypedTransaction,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let cap = U256::from(self.config.max_fee_per_gas_gwei) * U256::exp10(9);
+        let percent = U256::from(100 + self.config.fee_bump_percent);
+        let bump = |fee: Option<U256>| fee.unwrap_or_default() * percent / 100;
+
+        match (previous, tx) {
+            (TypedTransaction::Eip1559(previous), TypedTransaction::Eip1559(tx)) => {
+                let (estimated_max, estimated_priority) =
+                    self.provider.estimate_eip1559_fees(None).await?;
+                let old_max = previous.max_fee_per_gas.unwrap_or_default();
+
+                let max_fee = bump(previous.max_fee_per_gas).max(estimated_max).min(cap);
+                if max_fee <= old_max {
+                    return Err(format!("Max fee already at cap of {} gwei", self.config.max_fee_per_gas_gwei).into());
+                }
+                let priority_fee = bump(previous.max_priority_fee_per_gas)
+                    .max(estimated_priority)
+                    .min(max_fee);
+
+                tx.max_fee_per_gas = Some(max_fee);
+                tx.max_priority_fee_per_gas = Some(priority_fee);
+            }
+            (previous, tx) => {
+                let estimated = self.provider.get_gas_price().await?;
+                let old_price = previous.gas_price().unwrap_or_default();
+
+                let gas_price = bump(previous.gas_price()).max(estimated).min(cap);
+                if gas_price <= old_price {
+                    return Err(format!("Gas price already at cap of {} gwei", self.config.max_fee_per_gas_gwei).into());
+                }
+                tx.set_gas_price(gas_price);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_transaction(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<Transaction>, Box<dyn std::error::Error>> {
+        self.critical_read("eth_getTransactionByHash", serde_json::json!([tx_hash]))
+            .await
+    }
+
+    pub async fn get_transaction_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn std::error::Error>> {
+        self.critical_read("eth_getTransactionReceipt", serde_json::json!([tx_hash]))
+            .await
+    }
+
+    /// Monta o `Marketplace.cancelListing` que o vendedor assina; o contrato
+    /// devolve o NFT à carteira dele.
+    pub fn prepare_cancel_listing(
+        &self,
+        seller: Address,
+        nft_contract: Address,
+        token_id: U256,
+    ) -> Result<PreparedTx, Box<dyn std::error::Error>> {
+        let marketplace = Marketplace::new(self.marketplace_address()?, self.provider.clone());
+
+        let call = marketplace.cancel_listing(nft_contract, token_id);
+        Ok(self.prepared("cancelListing", seller, &call.tx))
+    }
+
+    /// Recibo de uma transação enviada por uma carteira de usuário e os
+    /// eventos do Marketplace que ela emitiu, com o índice do log de cada um;
+    /// `None` enquanto não for minerada.
+    pub async fn marketplace_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<(TransactionReceipt, Vec<(u64, MarketplaceEvents)>)>, Box<dyn std::error::Error>> {
+        let marketplace_address = self.marketplace_address()?;
+        let Some(receipt) = self.get_transaction_receipt(tx_hash).await? else {
+            return Ok(None);
+        };
+
+        let events = receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == marketplace_address)
+            .filter_map(|log| {
+                let event = parse_log::<MarketplaceEvents>(log.clone()).ok()?;
+                Some((log.log_index.unwrap_or_default().as_u64(), event))
+            })
+            .collect();
+        Ok(Some((receipt, events)))
+    }
+
+    fn prepared(&self, method: &'static str, from: Address, tx: &TypedTransaction) -> PreparedTx {
+        PreparedTx {
+            method,
+            chain_id: self.chain_id(),
+            from,
+            to: tx.to_addr().copied().unwrap_or_default(),
+            data: tx.data().cloned().unwrap_or_default(),
+            value: tx.value().copied().unwrap_or_default(),
+        }
+    }
+
+    /// Taxa atual do Marketplace (em pontos-base) e o saldo acumulado no
+    /// contrato, que é o que `withdrawFees` transfere ao dono.
+    pub async fn marketplace_fees(&self) -> Result<MarketplaceFees, Box<dyn std::error::Error>> {
+        let marketplace_address = self.marketplace_address()?;
+        let marketplace = Marketplace::new(marketplace_address, self.provider.clone());
+
+        Ok(MarketplaceFees {
+            fee_bps: marketplace.marketplace_fee().call().await?,
+            accrued: self.provider.get_balance(marketplace_address, None).await?,
+        })
+    }
+
+    /// Altera a taxa via `Marketplace.updateMarketplaceFee`; o contrato
+    /// limita a 1000 pontos-base (10%).
+    pub async fn update_marketplace_fee(&self, fee_bps: U256) -> Result<H256, Box<dyn std::error::Error>> {
+        let marketplace = Marketplace::new(self.marketplace_address()?, self.signer()?);
+
+        let call = marketplace.update_marketplace_fee(fee_bps);
+        let operation = self.submit(OperationKind::UpdateMarketplaceFee, call).await?;
+        let receipt = self.wait_for_operation(&operation).await?;
+        ensure_success(&receipt, "updateMarketplaceFee")?;
+
+        info!("💸 Marketplace fee set to {} bps - TX: {:?}", fee_bps, receipt.transaction_hash);
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Transfere as taxas acumuladas para o dono do Marketplace (`withdrawFees`).
+    pub async fn withdraw_fees(&self) -> Result<H256, Box<dyn std::error::Error>> {
+        let marketplace = Marketplace::new(self.marketplace_address()?, self.signer()?);
+
+        let call = marketplace.withdraw_fees();
+        let operation = self.submit(OperationKind::WithdrawFees, call).await?;
+        let receipt = self.wait_for_operation(&operation).await?;
+        ensure_success(&receipt, "withdrawFees")?;
+
+        info!("💸 Marketplace fees withdrawn - TX: {:?}", receipt.transaction_hash);
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Com `token_id`, o contrato é tratado como ERC-721 e o saldo é 1 se o
+    /// endereço for o dono (`ownerOf`); sem ele, como token fungível de
+    /// participação (`balanceOf` e `decimals` do ERC-20).
+    pub async fn get_token_balance(
+        &self,
+        contract_address: Address,
+        holder: Address,
+        token_id: Option<U256>,
+    ) -> Result<TokenBalance, Box<dyn std::error::Error>> {
+        match token_id {
+            Some(token_id) => {
+                let contract = AssetToken::new(contract_address, self.provider.clone());
+                let owner = contract.owner_of(token_id).call().await?;
+                Ok(TokenBalance {
+                    standard: TokenStandard::Erc721,
+                    balance: if owner == holder { U256::one() } else { U256::zero() },
+                    decimals: 0,
+                })
+            }
+            None => {
+                let contract = ShareToken::new(contract_address, self.provider.clone());
+                let balance = contract.balance_of(holder).call().await?;
+                let decimals = contract.decimals().call().await?;
+                Ok(TokenBalance {
+                    standard: TokenStandard::Erc20,
+                    balance,
+                    decimals,
+                })
+            }
+        }
+    }
+
+    /// Quantidade de NFTs de um contrato ERC-721 que o endereço possui.
+    pub async fn nft_balance(
+        &self,
+        contract_address: Address,
+        holder: Address,
     ) -> Result<U256, Box<dyn std::error::Error>> {
-        // Simulated balance
-        Ok(U256::from(1000))
+        let contract = AssetToken::new(contract_address, self.provider.clone());
+        Ok(contract.balance_of(holder).call().await?)
     }
 
+    /// Consulta bloco, chain id e saldo da carteira de operação, guardando o
+    /// resultado para `health()` e `is_connected()`.
+    pub async fn check_health(&self) -> ChainHealth {
+        let started = std::time::Instant::now();
+        let result = self.probe().await;
+        let now = chrono::Utc::now();
+
+        let mut health = self.health.lock().unwrap();
+        health.checked_at = Some(now);
+        match result {
+            Ok((block, reported_chain_id, signer_balance)) => {
+                health.last_success_at = Some(now);
+                health.latest_block = Some(block);
+                health.latency_ms = Some(started.elapsed().as_millis() as u64);
+                health.chain_id_mismatch =
+                    (reported_chain_id != self.network.chain_id).then_some(reported_chain_id);
+                health.signer_balance_wei = signer_balance.map(|balance| balance.to_string());
+                health.consecutive_failures = 0;
+                health.last_error = None;
+            }
+            Err(e) => {
+                health.consecutive_failures += 1;
+                health.last_error = Some(e);
+            }
+        }
+        health.clone()
+    }
+
+    async fn probe(&self) -> Result<(u64, u64, Option<U256>), String> {
+        let block = self.provider.get_block_number().await.map_err(|e| e.to_string())?;
+        let chain_id = self.provider.get_chainid().await.map_err(|e| e.to_string())?;
+        let signer_balance = match &self.client {
+            Some(client) => Some(
+                self.provider
+                    .get_balance(client.address(), None)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            ),
+            None => None,
+        };
+
+        Ok((block.as_u64(), chain_id.as_u64(), signer_balance))
+    }
+
+    pub fn health(&self) -> ChainHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    /// Verdadeiro quando a última verificação bem-sucedida é recente (até
+    /// três intervalos do monitor) e o nó está na rede configurada.
     pub fn is_connected(&self) -> bool {
-        true // Simplified for demo
+        let health = self.health.lock().unwrap();
+        let stale_after = chrono::Duration::seconds(3 * self.config.health_check_interval_secs as i64);
+
+        health.chain_id_mismatch.is_none()
+            && health.consecutive_failures == 0
+            && health
+                .last_success_at
+                .is_some_and(|at| chrono::Utc::now() - at <= stale_after)
+    }
+}
+
+fn ensure_success(receipt: &TransactionReceipt, method: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if receipt.status != Some(U64::from(1)) {
+        return Err(format!("{} reverted - TX: {:?}", method, receipt.transaction_hash).into());
     }
+    Ok(())
 }
 
 // Função utilitária para validar endereços Ethereum
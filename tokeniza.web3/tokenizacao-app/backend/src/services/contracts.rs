@@ -0,0 +1,12 @@
+// Bindings tipados gerados a partir das ABIs em `abi/`, extraídas dos artefatos
+// compilados em `onchain/`. Ao alterar um contrato, atualize a ABI correspondente.
+// `ShareToken` cobre só as leituras do `IERC20Metadata` da OpenZeppelin, usadas
+// para os tokens fungíveis de participação.
+use ethers::contract::abigen;
+
+abigen!(
+    AssetToken, "./abi/AssetToken.json", derives(serde::Serialize);
+    Marketplace, "./abi/Marketplace.json", derives(serde::Serialize);
+    Waitlist, "./abi/Waitlist.json", derives(serde::Serialize);
+    ShareToken, "./abi/ShareToken.json";
+);
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::services::networks::ChainRegistry;
+
+/// Verifica periodicamente cada rede configurada; o resultado alimenta
+/// `BlockchainService::is_connected`, o `/blockchain/status` e a prontidão.
+pub struct HealthMonitor {
+    networks: Arc<ChainRegistry>,
+    interval: Duration,
+}
+
+impl HealthMonitor {
+    pub fn new(networks: Arc<ChainRegistry>, interval: Duration) -> Self {
+        Self { networks, interval }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("🩺 Monitor de saúde das redes iniciado (a cada {:?})", self.interval);
+
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                self.poll().await;
+            }
+        })
+    }
+
+    async fn poll(&self) {
+        for service in self.networks.all() {
+            let was_connected = service.is_connected();
+            let health = service.check_health().await;
+            let name = &service.network().name;
+
+            if let Some(reported) = health.chain_id_mismatch {
+                warn!(
+                    "Rede {}: nó informa chain id {} (esperado {})",
+                    name,
+                    reported,
+                    service.chain_id()
+                );
+            } else if let Some(error) = &health.last_error {
+                warn!("Rede {} indisponível: {}", name, error);
+            } else if !was_connected {
+                info!(
+                    "✅ Rede {} disponível no bloco {} ({} ms)",
+                    name,
+                    health.latest_block.unwrap_or_default(),
+                    health.latency_ms.unwrap_or_default()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,1268 @@
+use ethers::{
+    abi::RawLog,
+    contract::EthLogDecode,
+    types::{Address, Block, Filter, Log, H256},
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::config::IndexerConfig;
+use crate::db::{
+    AssetHistoryRepository, AssetRepository, ChainEventRepository, Db, DbPool, DocumentRepository, ListingRepository,
+    RepositoryError, TransactionRepository, TxOperationRepository,
+};
+use crate::models::{
+    Asset, AssetMetadata, AssetStatus, AssetStatusChange, AssetType, ChainEffect, ChainEvent, EffectEntity,
+    IndexedBlock, Listing, ListingStatus, Transaction, TransactionStatus,
+};
+use crate::services::blockchain::{BlockchainService, PlatformContracts};
+use crate::services::storage::DocumentStorage;
+use crate::services::contracts::{
+    AssetListedFilter, AssetMintedFilter, AssetSoldFilter, AssetTokenEvents, AssetVerifiedFilter,
+    ListingCancelledFilter, MarketplaceEvents, WaitlistEvents,
+};
+use crate::services::subscriptions::HeadWaiter;
+
+type IndexerResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+const CHECKPOINT: &str = "platform_contracts";
+
+/// Autor registrado no histórico de status para mudanças lidas da rede.
+const INDEXER_ACTOR: &str = "indexer";
+
+/// Chave de `metadata.additional_info` marcada pelo `AssetVerified`.
+const VERIFIED_KEY: &str = "verified_onchain";
+
+/// Lê os eventos dos contratos da plataforma (back-fill a partir de um bloco
+/// inicial e depois acompanhando novos blocos) e reflete o que aconteceu
+/// on-chain em ativos, ofertas e transações, mesmo quando o backend não foi o
+/// remetente.
+///
+/// Os blocos mais recentes (até `reorg_depth` abaixo do topo) têm número e
+/// hash gravados; quando a cadeia deixa de bater com eles, tudo o que foi
+/// derivado a partir do ponto de bifurcação é desfeito e reindexado.
+///
+/// Cada bloco é gravado numa transação: eventos, efeitos, o hash do bloco e
+/// o checkpoint entram juntos ou não entram.
+pub struct ChainIndexer {
+    db: DbPool,
+    blockchain_service: Arc<BlockchainService>,
+    storage: Arc<dyn DocumentStorage>,
+    chain_id: u64,
+    contracts: PlatformContracts,
+    start_block: Option<u64>,
+    batch_size: u64,
+    poll_interval: Duration,
+    reorg_depth: u64,
+}
+
+impl ChainIndexer {
+    pub fn new(
+        db: DbPool,
+        blockchain_service: Arc<BlockchainService>,
+        storage: Arc<dyn DocumentStorage>,
+        config: &IndexerConfig,
+    ) -> Self {
+        let contracts = blockchain_service.platform_contracts();
+
+        Self {
+            db,
+            storage,
+            chain_id: blockchain_service.chain_id(),
+            blockchain_service,
+            contracts,
+            start_block: config.start_block,
+            batch_size: config.batch_size.max(1),
+            poll_interval: Duration::from_secs(config.poll_interval_secs),
+            reorg_depth: config.reorg_depth.max(1),
+        }
+    }
+
+    fn addresses(&self) -> Vec<Address> {
+        [
+            self.contracts.asset_token,
+            self.contracts.marketplace,
+            self.contracts.waitlist,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    pub fn spawn(self) -> Option<tokio::task::JoinHandle<()>> {
+        if self.addresses().is_empty() {
+            warn!("Indexador desativado: nenhum endereço de contrato configurado");
+            return None;
+        }
+
+        Some(tokio::spawn(async move {
+            info!(
+                "📇 Indexador da rede {} iniciado para {:?}",
+                self.blockchain_service.network().name,
+                self.addresses()
+            );
+
+            let mut waiter = HeadWaiter::new(
+                self.blockchain_service.subscriptions(),
+                self.poll_interval,
+                true,
+            );
+            loop {
+                waiter.wait().await;
+                if let Err(e) = self.sync().await {
+                    warn!("Indexador ({}): {}", self.blockchain_service.network().name, e);
+                }
+            }
+        }))
+    }
+
+    async fn sync(&self) -> IndexerResult<()> {
+        let checkpoints = ChainEventRepository::new(self.db.clone(), self.chain_id);
+        let head = self
+            .blockchain_service
+            .get_block_number()
+            .await
+            .map_err(|e| e.to_string())?
+            .as_u64();
+
+        let mut next = match checkpoints.checkpoint(CHECKPOINT).await? {
+            Some(last) => {
+                if let Some(fork) = self.find_fork(last).await? {
+                    self.rollback(fork).await?;
+                    fork
+                } else {
+                    last + 1
+                }
+            }
+            None => self.start_block.unwrap_or(head),
+        };
+
+        while next <= head {
+            let to = (next + self.batch_size - 1).min(head);
+            self.index_range(next, to, head).await?;
+            checkpoints.prune(to.saturating_sub(self.reorg_depth)).await?;
+            next = to + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Primeiro bloco gravado que não pertence mais à cadeia canônica, ou
+    /// `None` se o último bloco indexado continua válido.
+    async fn find_fork(&self, last: u64) -> IndexerResult<Option<u64>> {
+        let blocks = ChainEventRepository::new(self.db.clone(), self.chain_id).blocks_until(last).await?;
+
+        find_fork(blocks, |number| async move {
+            Ok(self.fetch_block(number).await?.and_then(|block| block.hash))
+        })
+        .await
+    }
+
+    async fn rollback(&self, fork: u64) -> IndexerResult<()> {
+        rollback(&self.db, self.chain_id, self.storage.as_ref(), fork).await
+    }
+
+    async fn fetch_block(&self, block_number: u64) -> IndexerResult<Option<Block<H256>>> {
+        Ok(self
+            .blockchain_service
+            .get_block(block_number)
+            .await
+            .map_err(|e| e.to_string())?)
+    }
+
+    /// Número e hash dos blocos do intervalo que ainda podem sofrer reorg (e
+    /// sempre do último, que vira o checkpoint), conferindo que cada um
+    /// continua a cadeia já gravada.
+    async fn fetch_blocks(&self, from: u64, to: u64, head: u64) -> IndexerResult<Vec<IndexedBlock>> {
+        let events = ChainEventRepository::new(self.db.clone(), self.chain_id);
+        let first = from.max(head.saturating_sub(self.reorg_depth)).min(to);
+
+        let mut blocks = Vec::new();
+        let mut parent = match first.checked_sub(1) {
+            Some(previous) => events.find_block(previous).await?,
+            None => None,
+        };
+
+        for number in first..=to {
+            let block = self
+                .fetch_block(number)
+                .await?
+                .ok_or_else(|| format!("bloco {} indisponível", number))?;
+            let hash = block.hash.ok_or_else(|| format!("bloco {} sem hash", number))?;
+
+            if let Some(parent) = parent {
+                if parse_hash(&parent.block_hash) != block.parent_hash {
+                    return Err(format!(
+                        "bloco {} não continua o bloco {} gravado; reorg em andamento",
+                        number, parent.block_number
+                    )
+                    .into());
+                }
+            }
+
+            let indexed = IndexedBlock {
+                block_number: number,
+                block_hash: format!("{:?}", hash),
+                parent_hash: format!("{:?}", block.parent_hash),
+            };
+            parent = Some(indexed.clone());
+            blocks.push(indexed);
+        }
+
+        Ok(blocks)
+    }
+
+    async fn index_range(&self, from: u64, to: u64, head: u64) -> IndexerResult<()> {
+        let blocks = self.fetch_blocks(from, to, head).await?;
+
+        let filter = Filter::new()
+            .address(self.addresses())
+            .from_block(from)
+            .to_block(to);
+
+        let mut logs = self
+            .blockchain_service
+            .get_logs(&filter)
+            .await
+            .map_err(|e| e.to_string())?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        // Logs de um bloco diferente do que acabou de ser lido indicam um
+        // reorg entre as duas consultas; o lote é refeito na próxima rodada.
+        let stale = logs.iter().any(|log| {
+            let number = log.block_number.map(|n| n.as_u64());
+            let expected = blocks.iter().find(|block| Some(block.block_number) == number);
+            matches!(
+                (expected, log.block_hash),
+                (Some(expected), Some(actual)) if parse_hash(&expected.block_hash) != actual
+            )
+        });
+        if stale {
+            return Err(format!("reorg durante a leitura dos blocos {} a {}", from, to).into());
+        }
+
+        if !logs.is_empty() {
+            info!("📇 {} logs entre os blocos {} e {}", logs.len(), from, to);
+        }
+
+        // Só os blocos com logs, dentro da janela de reorg ou o último do
+        // intervalo precisam de escrita; os demais ficam cobertos pelo
+        // checkpoint seguinte.
+        let mut pending: BTreeMap<u64, (Option<IndexedBlock>, Vec<Log>)> = BTreeMap::new();
+        pending.entry(to).or_default();
+        for block in blocks {
+            let number = block.block_number;
+            pending.entry(number).or_default().0 = Some(block);
+        }
+        for log in logs {
+            // Logs pendentes não têm bloco; serão lidos quando minerados.
+            if let Some(number) = log.block_number {
+                pending.entry(number.as_u64()).or_default().1.push(log);
+            }
+        }
+
+        for (number, (block, logs)) in pending {
+            self.index_block(number, block, logs).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Aplica os logs de um bloco e avança o checkpoint até ele numa única
+    /// transação; se algo falhar, o bloco inteiro é refeito na próxima rodada.
+    async fn index_block(&self, number: u64, block: Option<IndexedBlock>, logs: Vec<Log>) -> IndexerResult<()> {
+        let tx = Db::begin(&self.db).await?;
+        {
+            let events = ChainEventRepository::new(&tx, self.chain_id);
+            if let Some(block) = block {
+                events.save_block(&block).await?;
+            }
+            for log in logs {
+                self.handle_log(&tx, log).await?;
+            }
+            events.save_checkpoint(CHECKPOINT, number).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn handle_log(&self, db: &Db, log: Log) -> IndexerResult<()> {
+        let Some(event) = self.decode(&log) else {
+            return Ok(());
+        };
+        let (Some(block_number), Some(tx_hash), Some(log_index)) =
+            (log.block_number, log.transaction_hash, log.log_index)
+        else {
+            // Logs pendentes não têm bloco; serão lidos quando minerados.
+            return Ok(());
+        };
+
+        let tx_hash = format!("{:?}", tx_hash);
+        let record = ChainEvent {
+            id: Uuid::new_v4(),
+            block_number: block_number.as_u64(),
+            tx_hash: tx_hash.clone(),
+            log_index: log_index.as_u64(),
+            contract: format!("{:?}", log.address),
+            event: event.name().to_string(),
+            data: event.data()?,
+            created_at: crate::db::now(),
+        };
+
+        let events = ChainEventRepository::new(db, self.chain_id);
+        if events.exists(&record.tx_hash, record.log_index).await? {
+            return Ok(());
+        }
+
+        let mut writer = EffectWriter::new(db.clone(), self.chain_id, record.block_number, record.log_index);
+        match event {
+            PlatformEvent::AssetMinted(e) => self.on_asset_minted(&mut writer, log.address, e).await?,
+            PlatformEvent::AssetVerified(e) => {
+                self.on_asset_verified(&mut writer, log.address, e).await?
+            }
+            PlatformEvent::AssetListed(e) => self.on_asset_listed(&mut writer, e, &tx_hash).await?,
+            PlatformEvent::AssetSold(e) => self.on_asset_sold(&mut writer, e, &tx_hash).await?,
+            PlatformEvent::ListingCancelled(e) => self.on_listing_cancelled(&mut writer, e).await?,
+            PlatformEvent::Other { .. } => {}
+        }
+
+        events.record(&record).await?;
+        Ok(())
+    }
+
+    fn decode(&self, log: &Log) -> Option<PlatformEvent> {
+        let raw = RawLog::from(log.clone());
+        let source = Some(log.address);
+
+        if source == self.contracts.asset_token {
+            return match AssetTokenEvents::decode_log(&raw).ok()? {
+                AssetTokenEvents::AssetMintedFilter(e) => Some(PlatformEvent::AssetMinted(e)),
+                AssetTokenEvents::AssetVerifiedFilter(e) => Some(PlatformEvent::AssetVerified(e)),
+                _ => None,
+            };
+        }
+
+        if source == self.contracts.marketplace {
+            return match MarketplaceEvents::decode_log(&raw).ok()? {
+                MarketplaceEvents::AssetListedFilter(e) => Some(PlatformEvent::AssetListed(e)),
+                MarketplaceEvents::AssetSoldFilter(e) => Some(PlatformEvent::AssetSold(e)),
+                MarketplaceEvents::ListingCancelledFilter(e) => {
+                    Some(PlatformEvent::ListingCancelled(e))
+                }
+                _ => None,
+            };
+        }
+
+        if source == self.contracts.waitlist {
+            return match WaitlistEvents::decode_log(&raw).ok()? {
+                WaitlistEvents::JoinedWaitlistFilter(e) => Some(PlatformEvent::Other {
+                    name: "JoinedWaitlist",
+                    data: serde_json::to_value(e).ok()?,
+                }),
+                _ => None,
+            };
+        }
+
+        None
+    }
+
+    async fn on_asset_minted(
+        &self,
+        writer: &mut EffectWriter,
+        contract: Address,
+        event: AssetMintedFilter,
+    ) -> IndexerResult<()> {
+        let assets = AssetRepository::new(writer.db.clone());
+        let token_address = format!("{:?}", contract);
+        let token_id = event.token_id.to_string();
+
+        if assets.find_by_token(self.chain_id, &token_address, &token_id).await?.is_some() {
+            return Ok(());
+        }
+
+        // Mints feitos por este backend usam um tokenURI terminado no id do
+        // ativo; nesse caso o ativo existente é vinculado em vez de duplicado.
+        let token_uri = self.blockchain_service.token_uri(event.token_id).await.ok();
+        let linked_id = token_uri
+            .as_deref()
+            .and_then(|uri| uri.rsplit('/').next())
+            .and_then(|id| Uuid::parse_str(id).ok());
+
+        if let Some(id) = linked_id {
+            if let Some(asset) = assets.find_by_id(id).await? {
+                if asset.token_id.is_none() {
+                    writer
+                        .modify_asset(id, |asset| {
+                            asset.token_address = Some(token_address.clone());
+                            asset.token_id = Some(token_id.clone());
+                            asset.chain_id = Some(self.chain_id);
+                            asset.status = AssetStatus::Tokenized;
+                        })
+                        .await?;
+                }
+                return Ok(());
+            }
+        }
+
+        let now = crate::db::now();
+        let mut additional_info = std::collections::HashMap::new();
+        additional_info.insert("source".to_string(), serde_json::json!("chain"));
+
+        let asset = Asset {
+            id: Uuid::new_v4(),
+            name: format!("AssetToken #{}", token_id),
+            description: "Ativo registrado diretamente on-chain".to_string(),
+            asset_type: AssetType::from_label(&event.asset_type),
+            value: event.asset_value.into(),
+            total_supply: 1,
+            available_supply: 1,
+            token_address: Some(token_address),
+            token_id: Some(token_id),
+            owner: format!("{:?}", event.owner),
+            metadata: AssetMetadata {
+                location: None,
+                valuation_date: now,
+                appraiser: None,
+                documents: vec![],
+                images: vec![],
+                additional_info,
+            },
+            status: AssetStatus::Tokenized,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            chain_id: Some(self.chain_id),
+        };
+        writer.create_asset(&asset).await?;
+
+        info!("📇 Ativo {} criado a partir do mint on-chain", asset.id);
+        Ok(())
+    }
+
+    async fn on_asset_verified(
+        &self,
+        writer: &mut EffectWriter,
+        contract: Address,
+        event: AssetVerifiedFilter,
+    ) -> IndexerResult<()> {
+        let assets = AssetRepository::new(writer.db.clone());
+        let token_address = format!("{:?}", contract);
+
+        if let Some(asset) = assets
+            .find_by_token(self.chain_id, &token_address, &event.token_id.to_string())
+            .await?
+        {
+            writer
+                .modify_asset(asset.id, |asset| {
+                    asset
+                        .metadata
+                        .additional_info
+                        .insert(VERIFIED_KEY.to_string(), serde_json::json!(true));
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn on_asset_listed(
+        &self,
+        writer: &mut EffectWriter,
+        event: AssetListedFilter,
+        tx_hash: &str,
+    ) -> IndexerResult<()> {
+        let assets = AssetRepository::new(writer.db.clone());
+        let listings = ListingRepository::new(writer.db.clone());
+
+        let Some(asset) = assets
+            .find_by_token(self.chain_id, &format!("{:?}", event.nft_contract), &event.token_id.to_string())
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if listings.find_by_tx_hash(tx_hash).await?.is_some()
+            || listings.find_active_by_asset(asset.id).await?.is_some()
+        {
+            return Ok(());
+        }
+
+        let listing = Listing {
+            id: Uuid::new_v4(),
+            asset_id: asset.id,
+            seller: format!("{:?}", event.seller),
+            price: event.price.into(),
+            quantity: 1,
+            status: ListingStatus::Active,
+            tx_hash: Some(tx_hash.to_string()),
+            created_at: crate::db::now(),
+            expires_at: None,
+        };
+        writer.create_listing(&listing).await?;
+        writer
+            .modify_asset(asset.id, |asset| asset.status = AssetStatus::Trading)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn on_asset_sold(
+        &self,
+        writer: &mut EffectWriter,
+        event: AssetSoldFilter,
+        tx_hash: &str,
+    ) -> IndexerResult<()> {
+        // Compras registradas pela API (e as antigas, enviadas pela carteira
+        // de operação com reenvios) são liquidadas pelo TxTracker.
+        if TransactionRepository::new(writer.db.clone())
+            .find_by_tx_hash(tx_hash)
+            .await?
+            .is_some()
+            || TxOperationRepository::new(writer.db.clone())
+                .find_by_attempt_hash(self.chain_id, tx_hash)
+                .await?
+                .is_some()
+        {
+            return Ok(());
+        }
+
+        let assets = AssetRepository::new(writer.db.clone());
+        let listings = ListingRepository::new(writer.db.clone());
+
+        let Some(asset) = assets
+            .find_by_token(self.chain_id, &format!("{:?}", event.nft_contract), &event.token_id.to_string())
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let now = crate::db::now();
+        let buyer = format!("{:?}", event.buyer);
+
+        if let Some(mut listing) = listings.find_active_by_asset(asset.id).await? {
+            let previous = listing.clone();
+            listing.status = ListingStatus::Sold;
+            writer.update_listing(&previous, &listing).await?;
+
+            let transaction = Transaction {
+                id: Uuid::new_v4(),
+                listing_id: listing.id,
+                buyer: buyer.clone(),
+                seller: format!("{:?}", event.seller),
+                price: event.price.into(),
+                quantity: listing.quantity,
+                tx_hash: Some(tx_hash.to_string()),
+                status: TransactionStatus::Confirmed,
+                created_at: now,
+                completed_at: Some(now),
+            };
+            writer.create_transaction(&transaction).await?;
+        }
+
+        writer
+            .modify_asset(asset.id, |asset| {
+                asset.owner = buyer.clone();
+                asset.status = AssetStatus::Sold;
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn on_listing_cancelled(
+        &self,
+        writer: &mut EffectWriter,
+        event: ListingCancelledFilter,
+    ) -> IndexerResult<()> {
+        let assets = AssetRepository::new(writer.db.clone());
+        let listings = ListingRepository::new(writer.db.clone());
+
+        let Some(asset) = assets
+            .find_by_token(self.chain_id, &format!("{:?}", event.nft_contract), &event.token_id.to_string())
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if let Some(mut listing) = listings.find_active_by_asset(asset.id).await? {
+            let previous = listing.clone();
+            listing.status = ListingStatus::Cancelled;
+            writer.update_listing(&previous, &listing).await?;
+        }
+
+        writer
+            .modify_asset(asset.id, |asset| asset.status = AssetStatus::Tokenized)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Primeiro bloco gravado (de `blocks`, do mais recente para o mais antigo)
+/// cujo hash não é mais o da cadeia canônica, ou `None` se o mais recente
+/// continua válido. Para no primeiro bloco que ainda confere.
+async fn find_fork<F, Fut>(blocks: Vec<IndexedBlock>, mut canonical_hash: F) -> IndexerResult<Option<u64>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = IndexerResult<Option<H256>>>,
+{
+    let mut fork = None;
+    for block in blocks {
+        if canonical_hash(block.block_number).await? == Some(parse_hash(&block.block_hash)) {
+            break;
+        }
+        fork = Some(block.block_number);
+    }
+
+    Ok(fork)
+}
+
+/// Desfaz, do mais recente para o mais antigo, os efeitos dos eventos a
+/// partir de `fork` e volta o checkpoint para reindexar esses blocos, tudo
+/// numa transação. Os arquivos dos documentos de ativos desfeitos saem do
+/// armazenamento depois do commit.
+async fn rollback(db: &DbPool, chain_id: u64, storage: &dyn DocumentStorage, fork: u64) -> IndexerResult<()> {
+    let tx = Db::begin(db).await?;
+    let mut orphaned = Vec::new();
+    {
+        let events = ChainEventRepository::new(&tx, chain_id);
+        let effects = events.effects_since(fork).await?;
+
+        warn!(
+            "🔀 Reorg detectado a partir do bloco {}: desfazendo {} alterações",
+            fork,
+            effects.len()
+        );
+
+        for effect in effects {
+            orphaned.extend(undo(&tx, effect).await?);
+        }
+
+        events.rewind(CHECKPOINT, fork).await?;
+    }
+    tx.commit().await?;
+
+    for key in orphaned {
+        if let Err(e) = storage.delete(&key).await {
+            warn!("Arquivo {} não foi removido do armazenamento: {}", key, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Desfaz um efeito; devolve as chaves de armazenamento dos documentos que
+/// saíram junto com um ativo criado pelo indexador.
+async fn undo(db: &Db, effect: ChainEffect) -> IndexerResult<Vec<String>> {
+    match (effect.entity, effect.previous) {
+        (EffectEntity::Asset, Some(previous)) => {
+            let previous: Asset = serde_json::from_value(previous)?;
+            let restored = AssetRepository::new(db.clone())
+                .modify(effect.entity_id, |asset| {
+                    restore_chain_fields(asset, &previous);
+                    Ok::<_, crate::db::RepositoryError>(())
+                })
+                .await?;
+
+            if let Some((current, asset)) = restored.filter(|(current, asset)| current.status != asset.status) {
+                AssetHistoryRepository::new(db.clone())
+                    .record(&AssetStatusChange::new(
+                        asset.id,
+                        Some(current.status),
+                        asset.status,
+                        INDEXER_ACTOR,
+                        Some(format!("reorg: bloco {} desfeito", effect.block_number)),
+                    ))
+                    .await?;
+            }
+        }
+        (EffectEntity::Asset, None) => {
+            // Histórico, documentos e ofertas (com suas compras) referenciam
+            // o ativo e precisam sair antes dele; as ofertas que não passaram
+            // pelo diário não foram desfeitas por efeitos anteriores.
+            TransactionRepository::new(db.clone())
+                .delete_by_asset(effect.entity_id)
+                .await?;
+            ListingRepository::new(db.clone())
+                .delete_by_asset(effect.entity_id)
+                .await?;
+            AssetHistoryRepository::new(db.clone())
+                .delete_by_asset(effect.entity_id)
+                .await?;
+            let documents = DocumentRepository::new(db.clone())
+                .delete_by_asset(effect.entity_id)
+                .await?;
+            AssetRepository::new(db.clone()).delete(effect.entity_id).await?;
+            return Ok(documents.into_iter().map(|document| document.storage_key).collect());
+        }
+        (EffectEntity::Listing, Some(previous)) => {
+            let listing: Listing = serde_json::from_value(previous)?;
+            ListingRepository::new(db.clone()).update(&listing).await?;
+        }
+        (EffectEntity::Listing, None) => {
+            // Compras registradas pela API para uma oferta que deixou de
+            // existir on-chain também não valem mais.
+            TransactionRepository::new(db.clone())
+                .delete_by_listing(effect.entity_id)
+                .await?;
+            ListingRepository::new(db.clone()).delete(effect.entity_id).await?;
+        }
+        (EffectEntity::Transaction, Some(previous)) => {
+            let transaction: Transaction = serde_json::from_value(previous)?;
+            TransactionRepository::new(db.clone()).update(&transaction).await?;
+        }
+        (EffectEntity::Transaction, None) => {
+            TransactionRepository::new(db.clone())
+                .delete(effect.entity_id)
+                .await?;
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+/// Volta ao valor de `previous` apenas os campos que o indexador escreve;
+/// nome, descrição e demais metadados editados pela API nesse meio-tempo
+/// são preservados.
+fn restore_chain_fields(asset: &mut Asset, previous: &Asset) {
+    asset.status = previous.status;
+    asset.owner = previous.owner.clone();
+    asset.token_address = previous.token_address.clone();
+    asset.token_id = previous.token_id.clone();
+    asset.chain_id = previous.chain_id;
+    match previous.metadata.additional_info.get(VERIFIED_KEY) {
+        Some(verified) => {
+            asset
+                .metadata
+                .additional_info
+                .insert(VERIFIED_KEY.to_string(), verified.clone());
+        }
+        None => {
+            asset.metadata.additional_info.remove(VERIFIED_KEY);
+        }
+    }
+}
+
+/// Aplica as escritas derivadas de um log registrando o estado anterior de
+/// cada linha, para que `ChainIndexer::rollback` possa desfazê-las. A API usa
+/// o mesmo diário para o que registra a partir de transações mineradas, na
+/// posição do log que a transação emitiu.
+pub(crate) struct EffectWriter {
+    db: Db,
+    chain_id: u64,
+    block_number: u64,
+    log_index: u64,
+    step: u64,
+}
+
+impl EffectWriter {
+    pub(crate) fn new(db: Db, chain_id: u64, block_number: u64, log_index: u64) -> Self {
+        Self {
+            db,
+            chain_id,
+            block_number,
+            log_index,
+            step: 0,
+        }
+    }
+
+    pub(crate) fn db(&self) -> &Db {
+        &self.db
+    }
+
+    async fn journal(
+        &mut self,
+        entity: EffectEntity,
+        entity_id: Uuid,
+        previous: Option<serde_json::Value>,
+    ) -> Result<(), RepositoryError> {
+        let effect = ChainEffect {
+            id: Uuid::new_v4(),
+            block_number: self.block_number,
+            log_index: self.log_index,
+            step: self.step,
+            entity,
+            entity_id,
+            previous,
+            created_at: crate::db::now(),
+        };
+        self.step += 1;
+
+        ChainEventRepository::new(self.db.clone(), self.chain_id)
+            .record_effect(&effect)
+            .await
+    }
+
+    async fn create_asset(&mut self, asset: &Asset) -> Result<(), RepositoryError> {
+        self.journal(EffectEntity::Asset, asset.id, None).await?;
+        AssetRepository::new(self.db.clone()).create(asset).await?;
+        self.record_status(asset.id, None, asset.status, INDEXER_ACTOR, self.block_reason())
+            .await
+    }
+
+    /// Altera só o que `change` toca, sobre a versão mais recente do ativo,
+    /// e guarda a anterior para o rollback.
+    async fn modify_asset(&mut self, id: Uuid, change: impl FnMut(&mut Asset)) -> Result<(), RepositoryError> {
+        let reason = self.block_reason();
+        self.modify_asset_by(id, change, INDEXER_ACTOR, reason).await?;
+        Ok(())
+    }
+
+    /// Como `modify_asset`, com o autor e o motivo gravados no histórico se
+    /// o status mudar. Devolve a versão anterior e a nova.
+    pub(crate) async fn modify_asset_by(
+        &mut self,
+        id: Uuid,
+        mut change: impl FnMut(&mut Asset),
+        changed_by: &str,
+        reason: Option<String>,
+    ) -> Result<Option<(Asset, Asset)>, RepositoryError> {
+        let Some((previous, asset)) = AssetRepository::new(self.db.clone())
+            .modify(id, |asset| {
+                change(asset);
+                Ok::<_, RepositoryError>(())
+            })
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.journal(EffectEntity::Asset, id, Some(serde_json::to_value(&previous)?))
+            .await?;
+        if previous.status != asset.status {
+            self.record_status(asset.id, Some(previous.status), asset.status, changed_by, reason)
+                .await?;
+        }
+        Ok(Some((previous, asset)))
+    }
+
+    fn block_reason(&self) -> Option<String> {
+        Some(format!("bloco {}", self.block_number))
+    }
+
+    /// O que vem da rede é gravado como aconteceu, sem passar por
+    /// `AssetStatus::can_transition_to`.
+    async fn record_status(
+        &self,
+        asset_id: Uuid,
+        from: Option<AssetStatus>,
+        to: AssetStatus,
+        changed_by: &str,
+        reason: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        AssetHistoryRepository::new(self.db.clone())
+            .record(&AssetStatusChange::new(asset_id, from, to, changed_by, reason))
+            .await
+    }
+
+    pub(crate) async fn create_listing(&mut self, listing: &Listing) -> Result<(), RepositoryError> {
+        self.journal(EffectEntity::Listing, listing.id, None).await?;
+        ListingRepository::new(self.db.clone()).create(listing).await
+    }
+
+    pub(crate) async fn update_listing(&mut self, previous: &Listing, listing: &Listing) -> Result<(), RepositoryError> {
+        self.journal(EffectEntity::Listing, listing.id, Some(serde_json::to_value(previous)?))
+            .await?;
+        ListingRepository::new(self.db.clone()).update(listing).await
+    }
+
+    async fn create_transaction(&mut self, transaction: &Transaction) -> Result<(), RepositoryError> {
+        self.journal(EffectEntity::Transaction, transaction.id, None)
+            .await?;
+        TransactionRepository::new(self.db.clone())
+            .create(transaction)
+            .await
+    }
+}
+
+enum PlatformEvent {
+    AssetMinted(AssetMintedFilter),
+    AssetVerified(AssetVerifiedFilter),
+    AssetListed(AssetListedFilter),
+    AssetSold(AssetSoldFilter),
+    ListingCancelled(ListingCancelledFilter),
+    Other {
+        name: &'static str,
+        data: serde_json::Value,
+    },
+}
+
+impl PlatformEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            PlatformEvent::AssetMinted(_) => "AssetMinted",
+            PlatformEvent::AssetVerified(_) => "AssetVerified",
+            PlatformEvent::AssetListed(_) => "AssetListed",
+            PlatformEvent::AssetSold(_) => "AssetSold",
+            PlatformEvent::ListingCancelled(_) => "ListingCancelled",
+            PlatformEvent::Other { name, .. } => name,
+        }
+    }
+
+    fn data(&self) -> Result<serde_json::Value, serde_json::Error> {
+        fn to_json<T: Serialize>(event: &T) -> Result<serde_json::Value, serde_json::Error> {
+            serde_json::to_value(event)
+        }
+
+        match self {
+            PlatformEvent::AssetMinted(e) => to_json(e),
+            PlatformEvent::AssetVerified(e) => to_json(e),
+            PlatformEvent::AssetListed(e) => to_json(e),
+            PlatformEvent::AssetSold(e) => to_json(e),
+            PlatformEvent::ListingCancelled(e) => to_json(e),
+            PlatformEvent::Other { data, .. } => Ok(data.clone()),
+        }
+    }
+}
+
+fn parse_hash(raw: &str) -> H256 {
+    raw.parse().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AssetDocument, DocumentKind};
+    use crate::services::storage::{LocalStorage, StorageError};
+    use std::collections::HashMap;
+
+    const CHAIN_ID: u64 = 31337;
+
+    fn block(number: u64, hash: u64) -> IndexedBlock {
+        IndexedBlock {
+            block_number: number,
+            block_hash: format!("{:?}", H256::from_low_u64_be(hash)),
+            parent_hash: format!("{:?}", H256::from_low_u64_be(hash - 1)),
+        }
+    }
+
+    fn document(asset_id: Uuid) -> AssetDocument {
+        let id = Uuid::new_v4();
+        AssetDocument {
+            id,
+            asset_id,
+            kind: DocumentKind::Deed,
+            file_name: "escritura.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size: 3,
+            sha256: "00".repeat(32),
+            storage_key: format!("assets/{}/{}", asset_id, id),
+            uploaded_by: "0xabc".to_string(),
+            created_at: crate::db::now(),
+        }
+    }
+
+    fn local_storage() -> LocalStorage {
+        LocalStorage::new(std::env::temp_dir().join(format!("indexer-{}", Uuid::new_v4())))
+    }
+
+    #[tokio::test]
+    async fn find_fork_stops_at_the_first_block_still_canonical() {
+        let stored = vec![block(12, 120), block(11, 110), block(10, 100)];
+        let canonical = |hashes: HashMap<u64, u64>| {
+            move |number: u64| {
+                let hash = hashes.get(&number).map(|hash| H256::from_low_u64_be(*hash));
+                async move { Ok(hash) }
+            }
+        };
+
+        let unchanged = canonical(HashMap::from([(12, 120), (11, 110), (10, 100)]));
+        assert_eq!(find_fork(stored.clone(), unchanged).await.unwrap(), None);
+
+        let reorged = canonical(HashMap::from([(12, 121), (11, 111), (10, 100)]));
+        assert_eq!(find_fork(stored.clone(), reorged).await.unwrap(), Some(11));
+
+        // Bloco que a rede não conhece mais também foi descartado.
+        let shorter = canonical(HashMap::from([(11, 110), (10, 100)]));
+        assert_eq!(find_fork(stored, shorter).await.unwrap(), Some(12));
+    }
+
+    #[tokio::test]
+    async fn rollback_undoes_effects_from_the_fork_and_rewinds_the_checkpoint() {
+        let pool = crate::db::test_pool().await;
+        let assets = AssetRepository::new(pool.clone());
+        let storage = local_storage();
+
+        // Ativo da API, tokenizado no bloco 10 e listado no bloco 11.
+        let mut listed = crate::db::test_asset("0xabc");
+        listed.status = AssetStatus::Approved;
+        assets.create(&listed).await.unwrap();
+        let mut writer = EffectWriter::new(pool.clone().into(), CHAIN_ID, 10, 0);
+        writer
+            .modify_asset(listed.id, |asset| {
+                asset.token_address = Some("0x5fbdb2315678afecb367f032d93f642f64180aa3".to_string());
+                asset.token_id = Some("1".to_string());
+                asset.chain_id = Some(CHAIN_ID);
+                asset.status = AssetStatus::Tokenized;
+            })
+            .await
+            .unwrap();
+
+        let listing = Listing {
+            id: Uuid::new_v4(),
+            asset_id: listed.id,
+            seller: "0xabc".to_string(),
+            price: crate::units::Wei::from(1u64),
+            quantity: 1,
+            status: ListingStatus::Active,
+            tx_hash: Some(format!("{:?}", H256::from_low_u64_be(1))),
+            created_at: crate::db::now(),
+            expires_at: None,
+        };
+        let mut writer = EffectWriter::new(pool.clone().into(), CHAIN_ID, 11, 0);
+        writer.create_listing(&listing).await.unwrap();
+        writer
+            .modify_asset(listed.id, |asset| asset.status = AssetStatus::Trading)
+            .await
+            .unwrap();
+
+        // Ativo criado pelo indexador no bloco 11, que recebeu um documento.
+        let mut minted = crate::db::test_asset("0xdef");
+        minted.status = AssetStatus::Tokenized;
+        minted.chain_id = Some(CHAIN_ID);
+        let mut writer = EffectWriter::new(pool.clone().into(), CHAIN_ID, 11, 1);
+        writer.create_asset(&minted).await.unwrap();
+        let deed = document(minted.id);
+        DocumentRepository::new(pool.clone()).create(&deed).await.unwrap();
+        storage.put(&deed.storage_key, &deed.content_type, b"pdf".to_vec()).await.unwrap();
+
+        // Edição feita pela API depois dos eventos.
+        assets
+            .modify(listed.id, |asset| {
+                asset.name = "Apartamento Jardins reformado".to_string();
+                Ok::<_, crate::db::RepositoryError>(())
+            })
+            .await
+            .unwrap();
+
+        let events = ChainEventRepository::new(pool.clone(), CHAIN_ID);
+        events.save_checkpoint(CHECKPOINT, 12).await.unwrap();
+
+        rollback(&pool, CHAIN_ID, &storage, 11).await.unwrap();
+
+        let restored = assets.find_by_id(listed.id).await.unwrap().unwrap();
+        assert_eq!(restored.status, AssetStatus::Tokenized);
+        assert_eq!(restored.token_id.as_deref(), Some("1"));
+        assert_eq!(restored.name, "Apartamento Jardins reformado");
+        assert!(ListingRepository::new(pool.clone()).find_by_id(listing.id).await.unwrap().is_none());
+
+        assert!(assets.find_by_id(minted.id).await.unwrap().is_none());
+        assert!(DocumentRepository::new(pool.clone()).list_by_asset(minted.id).await.unwrap().is_empty());
+        assert!(matches!(storage.get(&deed.storage_key).await, Err(StorageError::NotFound)));
+
+        assert_eq!(events.checkpoint(CHECKPOINT).await.unwrap(), Some(10));
+        assert_eq!(events.effects_since(0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn undo_restores_only_the_fields_written_from_the_chain() {
+        let pool = crate::db::test_pool().await;
+        let assets = AssetRepository::new(pool.clone());
+        let mut asset = crate::db::test_asset("0xabc");
+        asset.status = AssetStatus::Trading;
+        assets.create(&asset).await.unwrap();
+
+        let mut writer = EffectWriter::new(pool.clone().into(), CHAIN_ID, 20, 0);
+        writer
+            .modify_asset(asset.id, |asset| {
+                asset.owner = "0xdef".to_string();
+                asset.status = AssetStatus::Sold;
+                asset.metadata.additional_info.insert(VERIFIED_KEY.to_string(), serde_json::json!(true));
+            })
+            .await
+            .unwrap();
+        assets
+            .modify(asset.id, |asset| {
+                asset.description = "Descrição revisada".to_string();
+                Ok::<_, crate::db::RepositoryError>(())
+            })
+            .await
+            .unwrap();
+
+        let effect = ChainEventRepository::new(pool.clone(), CHAIN_ID)
+            .effects_since(20)
+            .await
+            .unwrap()
+            .remove(0);
+        let orphaned = undo(&pool.clone().into(), effect).await.unwrap();
+
+        assert!(orphaned.is_empty());
+        let restored = assets.find_by_id(asset.id).await.unwrap().unwrap();
+        assert_eq!(restored.owner, "0xabc");
+        assert_eq!(restored.status, AssetStatus::Trading);
+        assert!(!restored.metadata.additional_info.contains_key(VERIFIED_KEY));
+        assert_eq!(restored.description, "Descrição revisada");
+    }
+
+    fn api_listing(asset_id: Uuid, tx_hash: u64) -> Listing {
+        Listing {
+            id: Uuid::new_v4(),
+            asset_id,
+            seller: "0xdef".to_string(),
+            price: crate::units::Wei::from(1u64),
+            quantity: 1,
+            status: ListingStatus::Active,
+            tx_hash: Some(format!("{:?}", H256::from_low_u64_be(tx_hash))),
+            created_at: crate::db::now(),
+            expires_at: None,
+        }
+    }
+
+    /// O que `create_listing` da API grava depois que o `listAsset` minerou.
+    async fn list_from_api(pool: &DbPool, listing: &Listing, block_number: u64) {
+        let tx = Db::begin(pool).await.unwrap();
+        let mut writer = EffectWriter::new(tx.clone(), CHAIN_ID, block_number, 0);
+        writer.create_listing(listing).await.unwrap();
+        writer
+            .modify_asset_by(
+                listing.asset_id,
+                |asset| asset.status = AssetStatus::Trading,
+                &listing.seller,
+                Some(format!("oferta {}", listing.id)),
+            )
+            .await
+            .unwrap();
+        drop(writer);
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rollback_removes_api_listings_and_purchases_of_an_indexed_asset() {
+        let pool = crate::db::test_pool().await;
+        let storage = local_storage();
+
+        let mut minted = crate::db::test_asset("0xdef");
+        minted.status = AssetStatus::Tokenized;
+        minted.chain_id = Some(CHAIN_ID);
+        EffectWriter::new(pool.clone().into(), CHAIN_ID, 11, 0)
+            .create_asset(&minted)
+            .await
+            .unwrap();
+
+        // Listado pela API no bloco 12, com uma compra em andamento, e uma
+        // oferta antiga que não passou pelo diário.
+        let listing = api_listing(minted.id, 2);
+        list_from_api(&pool, &listing, 12).await;
+        let purchase = Transaction {
+            id: Uuid::new_v4(),
+            listing_id: listing.id,
+            buyer: "0xbuyer".to_string(),
+            seller: listing.seller.clone(),
+            price: listing.price,
+            quantity: 1,
+            tx_hash: Some(format!("{:?}", H256::from_low_u64_be(3))),
+            status: TransactionStatus::Pending,
+            created_at: crate::db::now(),
+            completed_at: None,
+        };
+        TransactionRepository::new(pool.clone()).create(&purchase).await.unwrap();
+        let mut stale = api_listing(minted.id, 4);
+        stale.status = ListingStatus::Cancelled;
+        ListingRepository::new(pool.clone()).create(&stale).await.unwrap();
+
+        rollback(&pool, CHAIN_ID, &storage, 11).await.unwrap();
+
+        assert!(AssetRepository::new(pool.clone()).find_by_id(minted.id).await.unwrap().is_none());
+        let listings = ListingRepository::new(pool.clone());
+        assert!(listings.find_by_id(listing.id).await.unwrap().is_none());
+        assert!(listings.find_by_id(stale.id).await.unwrap().is_none());
+        assert!(TransactionRepository::new(pool.clone()).find_by_id(purchase.id).await.unwrap().is_none());
+        assert!(ChainEventRepository::new(pool.clone(), CHAIN_ID).effects_since(0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rollback_drops_an_api_listing_whose_transaction_left_the_chain() {
+        let pool = crate::db::test_pool().await;
+        let assets = AssetRepository::new(pool.clone());
+        let mut asset = crate::db::test_asset("0xdef");
+        asset.status = AssetStatus::Tokenized;
+        assets.create(&asset).await.unwrap();
+
+        let listing = api_listing(asset.id, 2);
+        list_from_api(&pool, &listing, 12).await;
+        assert_eq!(assets.find_by_id(asset.id).await.unwrap().unwrap().status, AssetStatus::Trading);
+
+        rollback(&pool, CHAIN_ID, &local_storage(), 12).await.unwrap();
+
+        assert!(ListingRepository::new(pool.clone()).find_by_id(listing.id).await.unwrap().is_none());
+        assert_eq!(assets.find_by_id(asset.id).await.unwrap().unwrap().status, AssetStatus::Tokenized);
+        let history = AssetHistoryRepository::new(pool.clone()).list_by_asset(asset.id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].changed_by, "0xdef");
+        assert_eq!(history[1].changed_by, INDEXER_ACTOR);
+    }
+
+    /// Reorg de verdade num Anvil com os contratos implantados e a
+    /// configuração no ambiente (`RPC_URL`, `CHAIN_ID`, `PRIVATE_KEY`,
+    /// `ASSET_TOKEN_ADDRESS`): `cargo test -- --ignored`.
+    #[tokio::test]
+    #[ignore]
+    async fn anvil_reorg_undoes_an_indexed_mint() {
+        use ethers::providers::{Http, Middleware, Provider};
+        use ethers::types::U256;
+
+        let pool = crate::db::test_pool().await;
+        let config = crate::config::AppConfig::new().expect("configuração do ambiente");
+        let service = crate::services::networks::ChainRegistry::new(&config.blockchain, pool.clone())
+            .await
+            .expect("rede configurada")
+            .default_service();
+        let rpc = Provider::<Http>::try_from(service.network().rpc_urls[0].as_str()).unwrap();
+        let storage: Arc<dyn DocumentStorage> = Arc::new(local_storage());
+
+        let head = rpc.get_block_number().await.unwrap().as_u64();
+        let indexer = ChainIndexer::new(
+            pool.clone(),
+            service.clone(),
+            storage.clone(),
+            &IndexerConfig {
+                enabled: true,
+                start_block: Some(head + 1),
+                batch_size: 100,
+                poll_interval_secs: 1,
+                reorg_depth: 64,
+            },
+        );
+        let snapshot: U256 = rpc.request("evm_snapshot", ()).await.unwrap();
+
+        // Conta #1 do Anvil; o tokenURI aponta para um ativo que não existe,
+        // então o indexador cria um.
+        let owner = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+        let token_uri = format!("http://localhost/api/v1/assets/{}", Uuid::new_v4());
+        let minted = service
+            .mint_asset(&crate::db::test_asset(owner), &token_uri)
+            .await
+            .map_err(|e| e.to_string())
+            .unwrap();
+        indexer.sync().await.unwrap();
+
+        let assets = AssetRepository::new(pool.clone());
+        let asset = assets
+            .find_by_token(
+                service.chain_id(),
+                &format!("{:?}", minted.contract_address),
+                &minted.token_id.to_string(),
+            )
+            .await
+            .unwrap()
+            .expect("mint indexado");
+        let deed = document(asset.id);
+        DocumentRepository::new(pool.clone()).create(&deed).await.unwrap();
+        storage.put(&deed.storage_key, &deed.content_type, b"pdf".to_vec()).await.unwrap();
+
+        let reverted: bool = rpc.request("evm_revert", [snapshot]).await.unwrap();
+        assert!(reverted);
+        let _: serde_json::Value = rpc.request("anvil_mine", [U256::from(3)]).await.unwrap();
+        indexer.sync().await.unwrap();
+
+        assert!(assets.find_by_id(asset.id).await.unwrap().is_none());
+        assert!(DocumentRepository::new(pool.clone()).list_by_asset(asset.id).await.unwrap().is_empty());
+        assert!(matches!(storage.get(&deed.storage_key).await, Err(StorageError::NotFound)));
+    }
+}
@@ -1 +1,11 @@
 pub mod blockchain;
+pub mod contracts;
+pub mod health_monitor;
+pub mod indexer;
+pub mod networks;
+pub mod nonce_manager;
+pub mod rpc;
+pub mod storage;
+pub mod subscriptions;
+pub mod tx_manager;
+pub mod tx_tracker;
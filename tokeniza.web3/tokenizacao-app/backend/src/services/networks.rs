@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::config::BlockchainConfig;
+use crate::db::DbPool;
+use crate::services::blockchain::BlockchainService;
+
+/// Um `BlockchainService` por rede configurada, endereçáveis pelo chain id
+/// ou pelo nome. Escritas e serviços em segundo plano usam a rede padrão.
+pub struct ChainRegistry {
+    services: Vec<Arc<BlockchainService>>,
+    default: usize,
+}
+
+impl ChainRegistry {
+    pub async fn new(config: &BlockchainConfig, db: DbPool) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut services = Vec::with_capacity(config.networks.len());
+        for network in &config.networks {
+            services.push(Arc::new(BlockchainService::new(network, config, db.clone()).await?));
+        }
+
+        let default_name = &config.default_network().name;
+        let default = services
+            .iter()
+            .position(|service| &service.network().name == default_name)
+            .unwrap_or(0);
+
+        Ok(Self { services, default })
+    }
+
+    pub fn default_service(&self) -> Arc<BlockchainService> {
+        self.services[self.default].clone()
+    }
+
+    pub fn get(&self, chain_id: u64) -> Option<Arc<BlockchainService>> {
+        self.services
+            .iter()
+            .find(|service| service.chain_id() == chain_id)
+            .cloned()
+    }
+
+    /// Aceita o nome da rede (`sepolia`) ou o chain id (`11155111`).
+    pub fn resolve(&self, chain: &str) -> Option<Arc<BlockchainService>> {
+        if let Ok(chain_id) = chain.parse::<u64>() {
+            return self.get(chain_id);
+        }
+
+        self.services
+            .iter()
+            .find(|service| service.network().name.eq_ignore_ascii_case(chain))
+            .cloned()
+    }
+
+    pub fn all(&self) -> &[Arc<BlockchainService>] {
+        &self.services
+    }
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use ethers::{
+    providers::{Middleware, MiddlewareError, PendingTransaction},
+    types::{transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, U256},
+};
+use serde::Serialize;
+use std::sync::Mutex;
+use thiserror::Error;
+use tracing::warn;
+
+/// Camada de middleware que distribui nonces localmente para a carteira de
+/// operação. Os envios são serializados: o nonce só avança quando o nó aceita
+/// a transação, e qualquer erro força uma nova leitura do nonce `pending` da
+/// rede, evitando lacunas e colisões entre requisições concorrentes.
+#[derive(Debug)]
+pub struct NonceManager<M> {
+    inner: M,
+    address: Address,
+    next: tokio::sync::Mutex<Option<U256>>,
+    stats: Mutex<NonceStats>,
+}
+
+/// Situação do gerenciador exposta em `/blockchain/status`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NonceStats {
+    pub next_nonce: Option<u64>,
+    pub submitted: u64,
+    pub resyncs: u64,
+    pub last_error: Option<String>,
+}
+
+impl<M: Middleware> NonceManager<M> {
+    pub fn new(inner: M, address: Address) -> Self {
+        Self {
+            inner,
+            address,
+            next: tokio::sync::Mutex::new(None),
+            stats: Mutex::new(NonceStats::default()),
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn stats(&self) -> NonceStats {
+        self.stats.lock().expect("nonce stats poisoned").clone()
+    }
+
+    async fn pending_nonce(&self) -> Result<U256, NonceManagerError<M>> {
+        self.inner
+            .get_transaction_count(self.address, Some(BlockId::Number(BlockNumber::Pending)))
+            .await
+            .map_err(MiddlewareError::from_err)
+    }
+
+    /// Atribui o próximo nonce, completa gas e taxas e envia. Devolve também a
+    /// transação exatamente como foi assinada, para que possa ser reenviada
+    /// com taxas maiores se ficar presa no mempool.
+    pub async fn submit(
+        &self,
+        mut tx: TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(PendingTransaction<'_, M::Provider>, TypedTransaction), NonceManagerError<M>> {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => self.pending_nonce().await?,
+        };
+        tx.set_nonce(nonce);
+
+        // Falhas aqui (estimativa de gas, revert simulado) acontecem antes do
+        // envio e não consomem o nonce.
+        self.inner
+            .fill_transaction(&mut tx, block)
+            .await
+            .map_err(MiddlewareError::from_err)?;
+
+        match self.inner.send_transaction(tx.clone(), block).await {
+            Ok(pending) => {
+                *next = Some(nonce + 1);
+                self.update_stats(|stats| {
+                    stats.next_nonce = Some((nonce + 1).as_u64());
+                    stats.submitted += 1;
+                });
+                Ok((pending, tx))
+            }
+            Err(err) => {
+                // O nó pode ter aceitado outra transação com este nonce (ou
+                // nenhuma); a fonte de verdade volta a ser a rede.
+                *next = self.pending_nonce().await.ok();
+                warn!(
+                    "Envio com nonce {} falhou, nonce ressincronizado para {:?}: {}",
+                    nonce, *next, err
+                );
+                let resynced = *next;
+                self.update_stats(|stats| {
+                    stats.next_nonce = resynced.map(|n| n.as_u64());
+                    stats.resyncs += 1;
+                    stats.last_error = Some(err.to_string());
+                });
+                Err(MiddlewareError::from_err(err))
+            }
+        }
+    }
+
+    fn update_stats(&self, update: impl FnOnce(&mut NonceStats)) {
+        update(&mut self.stats.lock().expect("nonce stats poisoned"));
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum NonceManagerError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> MiddlewareError for NonceManagerError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        NonceManagerError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            NonceManagerError::MiddlewareError(e) => Some(e),
+        }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for NonceManager<M> {
+    type Error = NonceManagerError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let tx = tx.into();
+
+        // Nonce explícito (substituição de uma transação travada): não passa
+        // pela fila local.
+        if tx.nonce().is_some() {
+            return self
+                .inner
+                .send_transaction(tx, block)
+                .await
+                .map_err(MiddlewareError::from_err);
+        }
+
+        self.submit(tx, block).await.map(|(pending, _)| pending)
+    }
+}
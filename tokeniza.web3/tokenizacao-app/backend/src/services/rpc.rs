@@ -0,0 +1,534 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ethers::providers::{
+    Http, HttpClientError, HttpRateLimitRetryPolicy, JsonRpcClient, JsonRpcError, ProviderError,
+    RetryPolicy, RpcError, Ws, WsClientError,
+};
+use ethers::types::U64;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Teto do intervalo entre tentativas e do tempo em que um endpoint com
+/// falhas seguidas fica fora da rotação.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Error, Debug)]
+pub enum FailoverError {
+    #[error(transparent)]
+    Http(#[from] HttpClientError),
+
+    #[error(transparent)]
+    Ws(#[from] WsClientError),
+
+    #[error("Todos os endpoints RPC falharam: {0}")]
+    Exhausted(String),
+
+    #[error("Sem quórum de {required} endpoints para {method}: {detail}")]
+    NoQuorum {
+        method: String,
+        required: usize,
+        detail: String,
+    },
+
+    #[error("Resposta inválida: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl RpcError for FailoverError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            FailoverError::Http(err) => err.as_error_response(),
+            FailoverError::Ws(err) => err.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            FailoverError::Http(err) => err.as_serde_error(),
+            FailoverError::Ws(err) => err.as_serde_error(),
+            FailoverError::Serialization(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FailoverError> for ProviderError {
+    fn from(err: FailoverError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(err))
+    }
+}
+
+/// Uso de um endpoint: quantas requisições atendeu (no total e por método),
+/// as falhas e se está temporariamente fora da rotação.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EndpointStats {
+    pub url: String,
+    pub healthy: bool,
+    pub served: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_served_at: Option<DateTime<Utc>>,
+    pub last_latency_ms: Option<u64>,
+    pub served_by_method: BTreeMap<String, u64>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    transport: Transport,
+    state: Mutex<EndpointState>,
+}
+
+/// URLs `ws://`/`wss://` só chegam aqui quando a rede não tem endpoint HTTP.
+/// A conexão é aberta na primeira requisição e refeita depois de cair.
+enum Transport {
+    Http(Http),
+    Ws {
+        reconnects: usize,
+        connection: tokio::sync::Mutex<Option<Ws>>,
+    },
+}
+
+impl Debug for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transport::Http(http) => http.fmt(f),
+            Transport::Ws { .. } => f.write_str("Ws"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    stats: EndpointStats,
+    unhealthy_until: Option<Instant>,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.unhealthy_until.is_none_or(|until| Instant::now() >= until)
+    }
+
+    fn record_success(&self, method: &str, latency: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.unhealthy_until = None;
+        let stats = &mut state.stats;
+        stats.served += 1;
+        stats.consecutive_failures = 0;
+        stats.last_served_at = Some(Utc::now());
+        stats.last_latency_ms = Some(latency.as_millis() as u64);
+        *stats.served_by_method.entry(method.to_string()).or_default() += 1;
+    }
+
+    fn record_failure(&self, error: &FailoverError, backoff: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.stats.failures += 1;
+        state.stats.consecutive_failures += 1;
+        state.stats.last_error = Some(error.to_string());
+
+        // Cada falha seguida dobra o tempo fora da rotação.
+        let exponent = state.stats.consecutive_failures.saturating_sub(1).min(16);
+        let cooldown = backoff.saturating_mul(1 << exponent).min(MAX_BACKOFF);
+        state.unhealthy_until = Some(Instant::now() + cooldown);
+    }
+
+    fn stats(&self) -> EndpointStats {
+        let state = self.state.lock().unwrap();
+        let mut stats = state.stats.clone();
+        stats.url = self.url.clone();
+        stats.healthy = state.unhealthy_until.is_none_or(|until| Instant::now() >= until);
+        stats
+    }
+}
+
+/// Transporte JSON-RPC sobre vários endpoints da mesma rede. Cada
+/// requisição vai para o primeiro endpoint saudável, na ordem configurada;
+/// erros transitórios tiram o endpoint da rotação por um tempo crescente e
+/// a requisição segue para o próximo, com espera exponencial entre rodadas.
+#[derive(Debug)]
+pub struct FailoverClient {
+    endpoints: Vec<Endpoint>,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl FailoverClient {
+    /// `ws_reconnects` vale para endpoints `ws://`/`wss://`, que reconectam
+    /// sozinhos esse número de vezes antes de uma conexão nova.
+    pub fn new(
+        urls: &[String],
+        max_retries: u32,
+        backoff: Duration,
+        ws_reconnects: usize,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if urls.is_empty() {
+            return Err("Nenhuma URL RPC configurada".into());
+        }
+
+        let endpoints = urls
+            .iter()
+            .map(|url| {
+                let transport = if url.starts_with("ws://") || url.starts_with("wss://") {
+                    Transport::Ws {
+                        reconnects: ws_reconnects,
+                        connection: tokio::sync::Mutex::default(),
+                    }
+                } else {
+                    Transport::Http(url.parse::<Http>()?)
+                };
+                Ok(Endpoint {
+                    url: url.clone(),
+                    transport,
+                    state: Mutex::default(),
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+        Ok(Self {
+            endpoints,
+            max_retries,
+            backoff,
+        })
+    }
+
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        self.endpoints.iter().map(Endpoint::stats).collect()
+    }
+
+    /// Saudáveis primeiro, na ordem configurada; os demais ficam como
+    /// último recurso para que uma falha geral não trave todas as chamadas.
+    fn rotation(&self) -> Vec<&Endpoint> {
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) =
+            self.endpoints.iter().partition(|endpoint| endpoint.is_healthy());
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn send<R: DeserializeOwned + Send>(
+        &self,
+        endpoint: &Endpoint,
+        method: &str,
+        params: &Value,
+    ) -> Result<R, FailoverError> {
+        let started = Instant::now();
+        let response = match &endpoint.transport {
+            Transport::Http(http) => http.request(method, params).await.map_err(FailoverError::from),
+            Transport::Ws {
+                reconnects,
+                connection,
+            } => send_ws(&endpoint.url, *reconnects, connection, method, params).await,
+        };
+        match response {
+            Ok(response) => {
+                endpoint.record_success(method, started.elapsed());
+                debug!("RPC {} atendido por {}", method, endpoint.url);
+                Ok(response)
+            }
+            Err(e) => {
+                if is_transient(&e) {
+                    endpoint.record_failure(&e, self.backoff);
+                    warn!("RPC {} falhou em {}: {}", method, endpoint.url, e);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Consulta todos os endpoints ao mesmo tempo e só devolve a resposta em que
+    /// pelo menos `quorum` deles concordam.
+    pub async fn request_quorum<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+        quorum: usize,
+    ) -> Result<R, FailoverError> {
+        let candidates: Vec<&Endpoint> = self.rotation();
+        let responses = futures::future::join_all(
+            candidates
+                .iter()
+                .map(|endpoint| self.send::<Value>(endpoint, method, &params)),
+        )
+        .await;
+
+        let mut votes: Vec<(Value, usize)> = Vec::new();
+        let mut errors = Vec::new();
+        for (endpoint, response) in candidates.iter().zip(responses) {
+            match response {
+                Ok(value) => match votes.iter_mut().find(|(other, _)| *other == value) {
+                    Some((_, count)) => *count += 1,
+                    None => votes.push((value, 1)),
+                },
+                Err(e) => errors.push(format!("{}: {}", endpoint.url, e)),
+            }
+        }
+
+        match votes.into_iter().max_by_key(|(_, count)| *count) {
+            Some((value, count)) if count >= quorum => Ok(serde_json::from_value(value)?),
+            best => Err(FailoverError::NoQuorum {
+                method: method.to_string(),
+                required: quorum,
+                detail: format!(
+                    "{} em acordo de {} endpoints{}",
+                    best.map_or(0, |(_, count)| count),
+                    candidates.len(),
+                    if errors.is_empty() { String::new() } else { format!("; {}", errors.join("; ")) }
+                ),
+            }),
+        }
+    }
+
+    /// Bloco mais alto que pelo menos `quorum` endpoints já alcançaram.
+    /// Leituras de estado com quórum são feitas nesse número e não em
+    /// `latest`: nós um bloco atrás uns dos outros responderiam diferente.
+    pub async fn quorum_block(&self, quorum: usize) -> Result<u64, FailoverError> {
+        let candidates: Vec<&Endpoint> = self.rotation();
+        let params = Value::Array(Vec::new());
+        let responses = futures::future::join_all(
+            candidates
+                .iter()
+                .map(|endpoint| self.send::<U64>(endpoint, "eth_blockNumber", &params)),
+        )
+        .await;
+
+        let mut heads = Vec::new();
+        let mut errors = Vec::new();
+        for (endpoint, response) in candidates.iter().zip(responses) {
+            match response {
+                Ok(head) => heads.push(head.as_u64()),
+                Err(e) => errors.push(format!("{}: {}", endpoint.url, e)),
+            }
+        }
+        heads.sort_unstable_by(|a, b| b.cmp(a));
+
+        heads.get(quorum.saturating_sub(1)).copied().ok_or_else(|| FailoverError::NoQuorum {
+            method: "eth_blockNumber".to_string(),
+            required: quorum,
+            detail: format!(
+                "{} de {} endpoints responderam{}",
+                heads.len(),
+                candidates.len(),
+                if errors.is_empty() { String::new() } else { format!("; {}", errors.join("; ")) }
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for FailoverClient {
+    type Error = FailoverError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)?;
+        let mut errors = Vec::new();
+
+        for round in 0..=self.max_retries {
+            errors.clear();
+            if round > 0 {
+                let delay = self.backoff.saturating_mul(1 << (round - 1).min(16)).min(MAX_BACKOFF);
+                tokio::time::sleep(delay).await;
+            }
+
+            for endpoint in self.rotation() {
+                match self.send(endpoint, method, &params).await {
+                    Ok(response) => return Ok(response),
+                    // Erros do próprio nó (revert, nonce baixo...) seriam
+                    // iguais em qualquer endpoint.
+                    Err(e) if !is_transient(&e) => return Err(e),
+                    Err(e) => errors.push(format!("{}: {}", endpoint.url, e)),
+                }
+            }
+        }
+
+        Err(FailoverError::Exhausted(errors.join("; ")))
+    }
+}
+
+async fn send_ws<R: DeserializeOwned + Send>(
+    url: &str,
+    reconnects: usize,
+    connection: &tokio::sync::Mutex<Option<Ws>>,
+    method: &str,
+    params: &Value,
+) -> Result<R, FailoverError> {
+    let ws = {
+        let mut connection = connection.lock().await;
+        match &*connection {
+            Some(ws) => ws.clone(),
+            None => {
+                let ws = Ws::connect_with_reconnects(url, reconnects).await?;
+                *connection = Some(ws.clone());
+                ws
+            }
+        }
+    };
+
+    match ws.request(method, params).await {
+        // Fora os erros do próprio nó, a conexão não serve mais: a próxima
+        // requisição abre outra.
+        Err(e) if !matches!(e, WsClientError::JsonRpcError(_)) => {
+            connection.lock().await.take();
+            Err(e.into())
+        }
+        response => Ok(response?),
+    }
+}
+
+fn is_transient(error: &FailoverError) -> bool {
+    match error {
+        FailoverError::Http(HttpClientError::ReqwestError(_) | HttpClientError::SerdeJson { .. }) => true,
+        FailoverError::Http(error @ HttpClientError::JsonRpcError(_)) => HttpRateLimitRetryPolicy.should_retry(error),
+        // Limites de taxa chegam pelo WebSocket com os mesmos códigos do HTTP.
+        FailoverError::Ws(WsClientError::JsonRpcError(error)) => {
+            HttpRateLimitRetryPolicy.should_retry(&HttpClientError::JsonRpcError(error.clone()))
+        }
+        FailoverError::Ws(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use serde_json::json;
+
+    /// Nó JSON-RPC falso: responde cada método com o valor de `results`, ou
+    /// com o erro quando o valor tem a chave `error`.
+    async fn node(results: Value) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| {
+                let results = results.clone();
+                async move {
+                    let method = request["method"].as_str().unwrap_or_default();
+                    let mut response = json!({ "jsonrpc": "2.0", "id": request["id"] });
+                    match results.get(method) {
+                        Some(result) if result.get("error").is_some() => {
+                            response["error"] = result["error"].clone();
+                        }
+                        Some(result) => response["result"] = result.clone(),
+                        None => {
+                            response["error"] = json!({ "code": -32601, "message": "method not found" });
+                        }
+                    }
+                    Json(response)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        url
+    }
+
+    /// Endereço em que ninguém escuta.
+    async fn dead_node() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn client(urls: &[String]) -> FailoverClient {
+        FailoverClient::new(urls, 0, Duration::from_millis(10), 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn request_fails_over_and_benches_the_failed_endpoint() {
+        let urls = [dead_node().await, node(json!({ "eth_chainId": "0x539" })).await];
+        let client = client(&urls);
+
+        for _ in 0..2 {
+            let chain_id: U64 = client.request("eth_chainId", ()).await.unwrap();
+            assert_eq!(chain_id.as_u64(), 1337);
+        }
+
+        let stats = client.stats();
+        // Fora da rotação, o primeiro não é tentado de novo na segunda chamada.
+        assert_eq!((stats[0].failures, stats[0].healthy), (1, false));
+        assert_eq!(stats[1].served, 2);
+    }
+
+    #[tokio::test]
+    async fn unreachable_websocket_fails_over_like_http() {
+        let dead = dead_node().await.replace("http://", "ws://");
+        let urls = [dead, node(json!({ "eth_chainId": "0x539" })).await];
+        let client = client(&urls);
+
+        let chain_id: U64 = client.request("eth_chainId", ()).await.unwrap();
+        assert_eq!(chain_id.as_u64(), 1337);
+        assert_eq!(client.stats()[0].failures, 1);
+    }
+
+    #[tokio::test]
+    async fn node_errors_are_not_retried_on_other_endpoints() {
+        let reverted = json!({ "error": { "code": 3, "message": "execution reverted" } });
+        let urls = [
+            node(json!({ "eth_call": reverted })).await,
+            node(json!({ "eth_call": "0x" })).await,
+        ];
+        let client = client(&urls);
+
+        let result: Result<Value, _> = client.request("eth_call", ()).await;
+        assert!(result.unwrap_err().as_error_response().is_some());
+        assert_eq!(client.stats()[1].served, 0);
+        assert!(client.stats()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn all_endpoints_down_is_exhausted() {
+        let client = client(&[dead_node().await, dead_node().await]);
+        let result: Result<Value, _> = client.request("eth_chainId", ()).await;
+        assert!(matches!(result, Err(FailoverError::Exhausted(_))));
+    }
+
+    #[tokio::test]
+    async fn quorum_needs_enough_matching_answers() {
+        let urls = [
+            node(json!({ "eth_getBalance": "0x1" })).await,
+            node(json!({ "eth_getBalance": "0x1" })).await,
+            node(json!({ "eth_getBalance": "0x2" })).await,
+            dead_node().await,
+        ];
+        let client = client(&urls);
+
+        let balance: U64 = client.request_quorum("eth_getBalance", json!([]), 2).await.unwrap();
+        assert_eq!(balance.as_u64(), 1);
+
+        let result: Result<U64, _> = client.request_quorum("eth_getBalance", json!([]), 3).await;
+        match result {
+            Err(FailoverError::NoQuorum { required, detail, .. }) => {
+                assert_eq!(required, 3);
+                assert!(detail.starts_with("2 em acordo de 4 endpoints"), "{}", detail);
+            }
+            other => panic!("esperava NoQuorum, veio {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn quorum_block_is_the_highest_head_enough_endpoints_reached() {
+        let urls = [
+            node(json!({ "eth_blockNumber": "0xc" })).await,
+            node(json!({ "eth_blockNumber": "0xa" })).await,
+            node(json!({ "eth_blockNumber": "0xb" })).await,
+            dead_node().await,
+        ];
+        let client = client(&urls);
+
+        assert_eq!(client.quorum_block(1).await.unwrap(), 12);
+        assert_eq!(client.quorum_block(2).await.unwrap(), 11);
+        assert_eq!(client.quorum_block(3).await.unwrap(), 10);
+        assert!(matches!(client.quorum_block(4).await, Err(FailoverError::NoQuorum { .. })));
+    }
+}
@@ -0,0 +1,184 @@
+use ethers::prelude::*;
+use ethers::providers::{Provider, Ws};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::time::Interval;
+use tracing::{info, warn};
+
+/// Teto da espera entre reconexões quando todos os endpoints WebSocket falham.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Assinaturas `newHeads` e de logs dos contratos da plataforma sobre os
+/// endpoints WebSocket de uma rede. O transporte do ethers já reconecta e
+/// reassina sozinho algumas vezes; quando desiste, esta tarefa abre uma nova
+/// conexão (passando para o próximo endpoint) e assina de novo.
+pub struct ChainSubscriptions {
+    urls: Vec<String>,
+    addresses: Vec<Address>,
+    reconnects: usize,
+    live: AtomicBool,
+    heads: watch::Sender<Option<u64>>,
+    logs: broadcast::Sender<Log>,
+}
+
+impl ChainSubscriptions {
+    pub fn new(urls: Vec<String>, addresses: Vec<Address>, reconnects: usize) -> Arc<Self> {
+        let (heads, _) = watch::channel(None);
+        let (logs, _) = broadcast::channel(256);
+
+        Arc::new(Self {
+            urls,
+            addresses,
+            reconnects,
+            live: AtomicBool::new(false),
+            heads,
+            logs,
+        })
+    }
+
+    pub fn spawn(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut failures: u32 = 0;
+            for url in this.urls.iter().cycle() {
+                match this.run(url).await {
+                    Ok(()) => {
+                        warn!("Assinatura WebSocket em {} encerrada", url);
+                        failures = 0;
+                    }
+                    Err(e) => {
+                        warn!("Assinatura WebSocket em {} falhou: {}", url, e);
+                        failures += 1;
+                    }
+                }
+                this.set_live(false);
+
+                let delay = Duration::from_secs(1)
+                    .saturating_mul(1 << failures.min(6))
+                    .min(MAX_RECONNECT_DELAY);
+                tokio::time::sleep(delay).await;
+            }
+        })
+    }
+
+    async fn run(&self, url: &str) -> Result<(), ProviderError> {
+        let provider = Provider::<Ws>::connect_with_reconnects(url, self.reconnects).await?;
+        let mut blocks = provider.subscribe_blocks().await?;
+        let mut logs = if self.addresses.is_empty() {
+            None
+        } else {
+            let filter = Filter::new().address(self.addresses.clone());
+            Some(provider.subscribe_logs(&filter).await?)
+        };
+
+        info!("📡 Assinando novos blocos e logs via {}", url);
+        self.set_live(true);
+
+        loop {
+            tokio::select! {
+                block = blocks.next() => match block {
+                    Some(block) => {
+                        self.heads.send_replace(block.number.map(|number| number.as_u64()));
+                    }
+                    None => return Ok(()),
+                },
+                log = next_log(&mut logs) => match log {
+                    Some(log) => {
+                        // Sem receptores ativos o envio falha, o que é esperado.
+                        let _ = self.logs.send(log);
+                    }
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+
+    /// Acorda quem espera por blocos também quando a conexão cai, para que
+    /// volte a usar o intervalo de consulta.
+    fn set_live(&self, live: bool) {
+        if self.live.swap(live, Ordering::SeqCst) != live {
+            self.heads.send_modify(|_| {});
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.live.load(Ordering::SeqCst)
+    }
+
+    pub fn latest_head(&self) -> Option<u64> {
+        *self.heads.borrow()
+    }
+}
+
+async fn next_log(
+    logs: &mut Option<SubscriptionStream<'_, Ws, Log>>,
+) -> Option<Log> {
+    match logs {
+        Some(logs) => logs.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Ponto de espera dos serviços em segundo plano: com a assinatura WebSocket
+/// ativa, acordam a cada novo bloco (ou log, se pedido); sem ela, no
+/// intervalo de consulta de sempre.
+pub struct HeadWaiter {
+    subscriptions: Option<Arc<ChainSubscriptions>>,
+    heads: Option<watch::Receiver<Option<u64>>>,
+    logs: Option<broadcast::Receiver<Log>>,
+    interval: Interval,
+}
+
+impl HeadWaiter {
+    pub fn new(
+        subscriptions: Option<Arc<ChainSubscriptions>>,
+        poll_interval: Duration,
+        with_logs: bool,
+    ) -> Self {
+        let heads = subscriptions.as_ref().map(|s| s.heads.subscribe());
+        let logs = subscriptions
+            .as_ref()
+            .filter(|_| with_logs)
+            .map(|s| s.logs.subscribe());
+
+        Self {
+            subscriptions,
+            heads,
+            logs,
+            interval: tokio::time::interval(poll_interval),
+        }
+    }
+
+    pub async fn wait(&mut self) {
+        let live = self.subscriptions.as_ref().is_some_and(|s| s.is_live());
+
+        match &mut self.heads {
+            Some(heads) if live => {
+                tokio::select! {
+                    _ = heads.changed() => {}
+                    _ = recv_log(&mut self.logs) => {}
+                }
+                heads.borrow_and_update();
+                // Vários logs do mesmo bloco valem uma única rodada.
+                if let Some(logs) = &mut self.logs {
+                    while logs.try_recv().is_ok() {}
+                }
+            }
+            _ => {
+                self.interval.tick().await;
+            }
+        }
+    }
+}
+
+async fn recv_log(logs: &mut Option<broadcast::Receiver<Log>>) {
+    match logs {
+        Some(logs) => {
+            let _ = logs.recv().await;
+        }
+        None => std::future::pending().await,
+    }
+}
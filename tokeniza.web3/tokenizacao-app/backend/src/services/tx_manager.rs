@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::db::{DbPool, TxOperationRepository};
+use crate::models::{AttemptReason, OperationStatus, TxOperation};
+use crate::services::blockchain::BlockchainService;
+use crate::services::subscriptions::HeadWaiter;
+
+/// Acompanha as operações enviadas pela carteira de operação. Quando nenhuma
+/// tentativa é minerada em `stuck_after_blocks` blocos, a operação é reenviada
+/// com o mesmo nonce e taxas maiores.
+pub struct TxManager {
+    db: DbPool,
+    blockchain_service: Arc<BlockchainService>,
+    stuck_after_blocks: u64,
+    poll_interval: Duration,
+}
+
+impl TxManager {
+    pub fn new(
+        db: DbPool,
+        blockchain_service: Arc<BlockchainService>,
+        stuck_after_blocks: u64,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            db,
+            blockchain_service,
+            stuck_after_blocks: stuck_after_blocks.max(1),
+            poll_interval,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(
+                "⛽ Tx manager iniciado em {} (reenvio após {} blocos sem mineração)",
+                self.blockchain_service.network().name,
+                self.stuck_after_blocks
+            );
+
+            let mut waiter = HeadWaiter::new(
+                self.blockchain_service.subscriptions(),
+                self.poll_interval,
+                false,
+            );
+            loop {
+                waiter.wait().await;
+                if let Err(e) = self.poll().await {
+                    warn!("Tx manager ({}): {}", self.blockchain_service.network().name, e);
+                }
+            }
+        })
+    }
+
+    async fn poll(&self) -> Result<(), String> {
+        let operations = TxOperationRepository::new(self.db.clone());
+        let pending = operations
+            .list_by_status(self.blockchain_service.chain_id(), &OperationStatus::Pending)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let head = self
+            .blockchain_service
+            .get_block_number()
+            .await
+            .map_err(|e| e.to_string())?
+            .as_u64();
+        let confirmed_nonce = self
+            .blockchain_service
+            .confirmed_nonce()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for operation in pending {
+            self.check(operation, head, confirmed_nonce).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn check(
+        &self,
+        mut operation: TxOperation,
+        head: u64,
+        confirmed_nonce: Option<u64>,
+    ) -> Result<(), String> {
+        let operations = TxOperationRepository::new(self.db.clone());
+        let attempts = operations
+            .attempts(operation.id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mined = self
+            .blockchain_service
+            .mined_attempt(&attempts)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some((attempt, _)) = mined {
+            let operation = self
+                .blockchain_service
+                .complete_operation(operation, &attempt)
+                .await
+                .map_err(|e| e.to_string())?;
+            info!(
+                "✅ Operação {:?} (nonce {}) concluída: {:?} - TX: {}",
+                operation.kind, operation.nonce, operation.status, operation.tx_hash
+            );
+            return Ok(());
+        }
+
+        // Nonce já consumido, mas por nenhuma das nossas tentativas.
+        if confirmed_nonce.is_some_and(|nonce| nonce > operation.nonce) {
+            operation.status = OperationStatus::Dropped;
+            operation.updated_at = crate::db::now();
+            operations.update(&operation).await.map_err(|e| e.to_string())?;
+            warn!("Operação {} (nonce {}) descartada", operation.id, operation.nonce);
+            return Ok(());
+        }
+
+        let Some(last) = attempts.last() else {
+            return Ok(());
+        };
+        if head.saturating_sub(last.submitted_block) < self.stuck_after_blocks {
+            return Ok(());
+        }
+
+        // Um cancelamento travado continua sendo cancelamento.
+        let reason = match last.reason {
+            AttemptReason::Cancel => AttemptReason::Cancel,
+            _ => AttemptReason::GasBump,
+        };
+        if let Err(e) = self
+            .blockchain_service
+            .replace_operation(&operation, reason)
+            .await
+            .map_err(|e| e.to_string())
+        {
+            warn!(
+                "Não foi possível reenviar o nonce {} ({:?}): {}",
+                operation.nonce, operation.kind, e
+            );
+        }
+
+        Ok(())
+    }
+}
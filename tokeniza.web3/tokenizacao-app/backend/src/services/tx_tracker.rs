@@ -0,0 +1,412 @@
+use ethers::types::{TransactionReceipt, H256, U64};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::{
+    AssetHistoryRepository, AssetRepository, Db, DbPool, ListingRepository, TransactionRepository,
+    TxOperationRepository,
+};
+use crate::models::{AssetStatus, AssetStatusChange, ListingStatus, OperationStatus, Transaction, TransactionStatus};
+use crate::services::blockchain::BlockchainService;
+use crate::services::subscriptions::HeadWaiter;
+
+/// Consultas seguidas em que o nó não conhece a transação antes de ela ser
+/// dada como descartada; uma só pode ser um nó atrasado ou atrás de um
+/// balanceador.
+const DROP_AFTER_MISSES: u32 = 3;
+
+/// Acompanha as transações `Pending` gravadas no banco, consultando o recibo
+/// de cada hash até atingir a profundidade de confirmação configurada.
+pub struct TxTracker {
+    db: DbPool,
+    blockchain_service: Arc<BlockchainService>,
+    confirmations: u64,
+    poll_interval: Duration,
+    drop_after: Duration,
+    /// Consultas seguidas sem a transação no nó, por compra.
+    misses: Mutex<HashMap<Uuid, u32>>,
+}
+
+impl TxTracker {
+    pub fn new(
+        db: DbPool,
+        blockchain_service: Arc<BlockchainService>,
+        confirmations: u64,
+        poll_interval: Duration,
+        drop_after: Duration,
+    ) -> Self {
+        Self {
+            db,
+            blockchain_service,
+            confirmations: confirmations.max(1),
+            poll_interval,
+            drop_after,
+            misses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(
+                "👀 Tx tracker iniciado em {} ({} confirmações, a cada {:?})",
+                self.blockchain_service.network().name,
+                self.confirmations,
+                self.poll_interval
+            );
+
+            let mut waiter = HeadWaiter::new(
+                self.blockchain_service.subscriptions(),
+                self.poll_interval,
+                false,
+            );
+            loop {
+                waiter.wait().await;
+                if let Err(e) = self.poll().await {
+                    warn!("Tx tracker ({}): {}", self.blockchain_service.network().name, e);
+                }
+            }
+        })
+    }
+
+    async fn poll(&self) -> Result<(), String> {
+        let transactions = TransactionRepository::new(self.db.clone());
+        let pending = transactions
+            .list_by_status(self.blockchain_service.chain_id(), &TransactionStatus::Pending)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        self.misses
+            .lock()
+            .unwrap()
+            .retain(|id, _| pending.iter().any(|transaction| transaction.id == *id));
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let head = self
+            .blockchain_service
+            .get_block_number()
+            .await
+            .map_err(|e| e.to_string())?
+            .as_u64();
+
+        for transaction in pending {
+            let Some(tx_hash) = transaction.tx_hash.as_deref().and_then(|h| h.parse::<H256>().ok())
+            else {
+                continue;
+            };
+
+            let receipt = match self.blockchain_service.get_transaction_receipt(tx_hash).await {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    warn!("Tx tracker: recibo de {:?} indisponível: {}", tx_hash, e);
+                    continue;
+                }
+            };
+            let Some(receipt) = receipt else {
+                self.follow_replacement(transaction).await.map_err(|e| e.to_string())?;
+                continue;
+            };
+
+            if confirmations(&receipt, head) < self.confirmations {
+                continue;
+            }
+
+            self.settle(transaction, &receipt).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Se a compra foi reenviada pelo `TxManager`, passa a acompanhar o hash
+    /// que de fato foi minerado; se o nonce foi cancelado, a compra falhou.
+    async fn follow_replacement(
+        &self,
+        mut transaction: Transaction,
+    ) -> Result<(), crate::db::RepositoryError> {
+        let Some(tx_hash) = transaction.tx_hash.clone() else {
+            return Ok(());
+        };
+        let Some(operation) = TxOperationRepository::new(self.db.clone())
+            .find_by_attempt_hash(self.blockchain_service.chain_id(), &tx_hash)
+            .await?
+        else {
+            return self.check_dropped(transaction, &tx_hash).await;
+        };
+
+        match operation.status {
+            OperationStatus::Mined if !operation.tx_hash.eq_ignore_ascii_case(&tx_hash) => {
+                info!("🔁 Transação {} substituída por {}", transaction.id, operation.tx_hash);
+                transaction.tx_hash = Some(operation.tx_hash);
+            }
+            OperationStatus::Cancelled | OperationStatus::Dropped => {
+                info!("❌ Transação {} não será minerada ({:?})", transaction.id, operation.status);
+                transaction.status = TransactionStatus::Failed;
+                transaction.completed_at = Some(crate::db::now());
+            }
+            _ => return Ok(()),
+        }
+
+        TransactionRepository::new(self.db.clone())
+            .update(&transaction)
+            .await
+    }
+
+    /// Compras assinadas pela carteira do comprador não passam pelo
+    /// `TxManager`: se o nó já não conhece a transação (substituída ou
+    /// descartada pela carteira), ela não será minerada. Só conta depois de
+    /// `drop_after` desde o registro e de `DROP_AFTER_MISSES` consultas
+    /// seguidas sem ela, já que a transação pode ainda estar se propagando.
+    async fn check_dropped(
+        &self,
+        mut transaction: Transaction,
+        tx_hash: &str,
+    ) -> Result<(), crate::db::RepositoryError> {
+        let Ok(hash) = tx_hash.parse::<H256>() else {
+            return Ok(());
+        };
+        match self.blockchain_service.get_transaction(hash).await {
+            Ok(None) => {}
+            Ok(Some(_)) => {
+                self.misses.lock().unwrap().remove(&transaction.id);
+                return Ok(());
+            }
+            Err(e) => {
+                warn!("Tx tracker: transação {} indisponível: {}", tx_hash, e);
+                return Ok(());
+            }
+        }
+
+        let misses = {
+            let mut misses = self.misses.lock().unwrap();
+            let count = misses.entry(transaction.id).or_default();
+            *count += 1;
+            *count
+        };
+        let age = (crate::db::now() - transaction.created_at).to_std().unwrap_or_default();
+        if misses < DROP_AFTER_MISSES || age < self.drop_after {
+            return Ok(());
+        }
+
+        // Pode ter sido minerada entre a consulta do recibo e a da transação.
+        match self.blockchain_service.get_transaction_receipt(hash).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return Ok(()),
+            Err(e) => {
+                warn!("Tx tracker: recibo de {} indisponível: {}", tx_hash, e);
+                return Ok(());
+            }
+        }
+
+        self.misses.lock().unwrap().remove(&transaction.id);
+        info!(
+            "❌ Transação {} fora do nó há {} consultas e {:?} desde o registro",
+            transaction.id, misses, age
+        );
+        transaction.status = TransactionStatus::Failed;
+        transaction.completed_at = Some(crate::db::now());
+        TransactionRepository::new(self.db.clone())
+            .update(&transaction)
+            .await
+    }
+
+    async fn settle(
+        &self,
+        mut transaction: Transaction,
+        receipt: &TransactionReceipt,
+    ) -> Result<(), String> {
+        let succeeded = receipt.status == Some(U64::from(1));
+        let buyer = if succeeded {
+            self.buyer(&transaction, receipt).await?
+        } else {
+            None
+        };
+
+        transaction.status = if succeeded {
+            TransactionStatus::Confirmed
+        } else {
+            TransactionStatus::Failed
+        };
+        transaction.completed_at = Some(crate::db::now());
+
+        // Status da compra e efeitos no ativo entram juntos: o tracker só
+        // volta a olhar transações `Pending`, então uma compra `Confirmed`
+        // sem o ativo transferido nunca seria corrigida.
+        let write = async {
+            let tx = Db::begin(&self.db).await?;
+            TransactionRepository::new(&tx).update(&transaction).await?;
+            if let Some(buyer) = &buyer {
+                settle_purchase(&tx, &transaction, buyer).await?;
+            }
+            tx.commit().await
+        };
+        write.await.map_err(|e| e.to_string())?;
+
+        info!(
+            "{} Transação {} {:?} no bloco {:?}",
+            if succeeded { "✅" } else { "❌" },
+            transaction.id,
+            transaction.status,
+            receipt.block_number
+        );
+
+        Ok(())
+    }
+
+    /// Novo dono do NFT: o comprador do `AssetSold` no recibo ou, sem o
+    /// evento, o `ownerOf` atual.
+    async fn buyer(
+        &self,
+        transaction: &Transaction,
+        receipt: &TransactionReceipt,
+    ) -> Result<Option<String>, String> {
+        let Some(listing) = ListingRepository::new(self.db.clone())
+            .find_by_id(transaction.listing_id)
+            .await
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(None);
+        };
+        let Some(asset) = AssetRepository::new(self.db.clone())
+            .find_by_id(listing.asset_id)
+            .await
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(None);
+        };
+        let token = asset.token_address.as_deref().and_then(|a| a.parse().ok()).zip(
+            asset
+                .token_id
+                .as_deref()
+                .and_then(|id| ethers::types::U256::from_dec_str(id).ok()),
+        );
+        let Some((nft_contract, token_id)) = token else {
+            return Ok(None);
+        };
+
+        let buyer = match self
+            .blockchain_service
+            .purchase_buyer(receipt, nft_contract, token_id)
+            .map_err(|e| e.to_string())?
+        {
+            Some(buyer) => buyer,
+            None => self
+                .blockchain_service
+                .nft_owner(nft_contract, token_id)
+                .await
+                .map_err(|e| e.to_string())?,
+        };
+        Ok(Some(format!("{:?}", buyer)))
+    }
+}
+
+/// Aplica os efeitos de uma compra confirmada: a oferta é encerrada e o
+/// ativo passa para quem recebeu o NFT on-chain.
+async fn settle_purchase(
+    db: &Db,
+    transaction: &Transaction,
+    buyer: &str,
+) -> Result<(), crate::db::RepositoryError> {
+    let listings = ListingRepository::new(db);
+    let Some(mut listing) = listings.find_by_id(transaction.listing_id).await? else {
+        return Ok(());
+    };
+
+    listing.status = ListingStatus::Sold;
+    listings.update(&listing).await?;
+
+    let sold = AssetRepository::new(db)
+        .modify(listing.asset_id, |asset| {
+            asset.owner = buyer.to_string();
+            asset.status = AssetStatus::Sold;
+            Ok::<_, crate::db::RepositoryError>(())
+        })
+        .await?;
+    // Reprocessar a mesma compra não grava um Sold → Sold no histórico.
+    if let Some((previous, asset)) = sold.filter(|(previous, asset)| previous.status != asset.status) {
+        AssetHistoryRepository::new(db)
+            .record(&AssetStatusChange::new(
+                asset.id,
+                Some(previous.status),
+                asset.status,
+                "tx_tracker",
+                transaction.tx_hash.as_ref().map(|hash| format!("compra {} confirmada", hash)),
+            ))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Número de confirmações de um recibo dado o bloco mais recente
+/// (o bloco de inclusão conta como a primeira).
+pub fn confirmations(receipt: &TransactionReceipt, head: u64) -> u64 {
+    receipt
+        .block_number
+        .map(|block| head.saturating_sub(block.as_u64()) + 1)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_asset, test_pool};
+    use crate::models::Listing;
+
+    #[tokio::test]
+    async fn settling_twice_transfers_the_asset_once() {
+        let db = test_pool().await;
+        let mut asset = test_asset("0xseller");
+        asset.status = AssetStatus::Trading;
+        AssetRepository::new(db.clone()).create(&asset).await.unwrap();
+        let listing = Listing {
+            id: Uuid::new_v4(),
+            asset_id: asset.id,
+            seller: "0xseller".to_string(),
+            price: 1u64.into(),
+            quantity: 1,
+            status: ListingStatus::Active,
+            tx_hash: None,
+            created_at: crate::db::now(),
+            expires_at: None,
+        };
+        ListingRepository::new(db.clone()).create(&listing).await.unwrap();
+        let transaction = Transaction {
+            id: Uuid::new_v4(),
+            listing_id: listing.id,
+            buyer: "0xbuyer".to_string(),
+            seller: "0xseller".to_string(),
+            price: 1u64.into(),
+            quantity: 1,
+            tx_hash: Some(format!("{:?}", H256::repeat_byte(1))),
+            status: TransactionStatus::Confirmed,
+            created_at: crate::db::now(),
+            completed_at: Some(crate::db::now()),
+        };
+
+        // Sem commit, nada do que a compra escreveu fica no banco.
+        let tx = Db::begin(&db).await.unwrap();
+        settle_purchase(&tx, &transaction, "0xbuyer").await.unwrap();
+        drop(tx);
+        let stored = AssetRepository::new(db.clone()).find_by_id(asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.owner, "0xseller");
+
+        for _ in 0..2 {
+            let tx = Db::begin(&db).await.unwrap();
+            settle_purchase(&tx, &transaction, "0xbuyer").await.unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let stored = AssetRepository::new(db.clone()).find_by_id(asset.id).await.unwrap().unwrap();
+        assert_eq!(stored.owner, "0xbuyer");
+        assert_eq!(stored.status, AssetStatus::Sold);
+        let listing = ListingRepository::new(db.clone()).find_by_id(listing.id).await.unwrap().unwrap();
+        assert!(matches!(listing.status, ListingStatus::Sold));
+        let history = AssetHistoryRepository::new(db.clone()).list_by_asset(asset.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].from_status, Some(AssetStatus::Trading));
+    }
+}
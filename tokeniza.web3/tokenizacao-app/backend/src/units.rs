@@ -0,0 +1,341 @@
+// Conversão exata entre quantidades inteiras (wei, menor unidade de um token)
+// e strings decimais, para qualquer número de casas.
+
+use ethers::types::U256;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::handlers::error::ApiError;
+
+pub const ETHER_DECIMALS: u8 = 18;
+pub const GWEI_DECIMALS: u8 = 9;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UnitsError {
+    #[error("valor vazio")]
+    Empty,
+
+    #[error("'{0}' não é um número decimal não negativo")]
+    Invalid(String),
+
+    #[error("'{value}' tem mais de {decimals} casas decimais")]
+    TooManyDecimals { value: String, decimals: u8 },
+
+    #[error("'{0}' excede o limite de 256 bits")]
+    Overflow(String),
+}
+
+impl From<UnitsError> for ApiError {
+    fn from(err: UnitsError) -> Self {
+        ApiError::Validation(format!("Valor inválido: {}", err))
+    }
+}
+
+/// `1500000000000000000` com 18 casas vira `"1.5"`; zeros à direita da
+/// parte fracionária são omitidos.
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Inverso de `format_units`: `"1.5"` com 18 casas vira
+/// `1500000000000000000`. Rejeita sinais, expoentes, casas além de
+/// `decimals` (que seriam perdidas) e valores acima de 2^256 - 1.
+pub fn parse_units(value: &str, decimals: u8) -> Result<U256, UnitsError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(UnitsError::Empty);
+    }
+
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(UnitsError::Invalid(value.to_string()));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(UnitsError::TooManyDecimals {
+            value: value.to_string(),
+            decimals,
+        });
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+
+    U256::from_dec_str(digits).map_err(|_| UnitsError::Overflow(value.to_string()))
+}
+
+pub fn format_ether(wei: U256) -> String {
+    format_units(wei, ETHER_DECIMALS)
+}
+
+pub fn format_gwei(wei: U256) -> String {
+    format_units(wei, GWEI_DECIMALS)
+}
+
+pub fn parse_ether(value: &str) -> Result<U256, UnitsError> {
+    parse_units(value, ETHER_DECIMALS)
+}
+
+/// Quantidade em wei com a faixa completa de um `uint256`. Sai no JSON como
+/// string decimal (`"5000000000000000000000000"`) e entra como string ou,
+/// por compatibilidade, como número inteiro que caiba em u64.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wei(pub U256);
+
+impl Wei {
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl From<U256> for Wei {
+    fn from(value: U256) -> Self {
+        Wei(value)
+    }
+}
+
+impl From<u64> for Wei {
+    fn from(value: u64) -> Self {
+        Wei(U256::from(value))
+    }
+}
+
+impl From<Wei> for U256 {
+    fn from(value: Wei) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Wei {
+    type Err = UnitsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_units(value, 0).map(Wei)
+    }
+}
+
+impl Serialize for Wei {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Wei {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct WeiVisitor;
+
+        impl de::Visitor<'_> for WeiVisitor {
+            type Value = Wei;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("uma quantidade inteira em wei, de preferência como string decimal")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Wei, E> {
+                Ok(Wei::from(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Wei, E> {
+                u64::try_from(value)
+                    .map(Wei::from)
+                    .map_err(|_| E::custom(UnitsError::Invalid(value.to_string())))
+            }
+
+            // Números acima de u64 chegam como f64 e já perderam precisão.
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Wei, E> {
+                Err(E::custom(format!(
+                    "'{}' deve ser enviado como string decimal para não perder precisão",
+                    value
+                )))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Wei, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(WeiVisitor)
+    }
+}
+
+/// Acrescenta `<campo>_eth` ao lado de cada campo em wei de um objeto JSON
+/// (ou de cada objeto de uma lista), mantendo o valor original intacto.
+pub fn with_ether(mut value: Value, fields: &[&str]) -> Value {
+    match &mut value {
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                *item = with_ether(item.take(), fields);
+            }
+        }
+        Value::Object(object) => {
+            for field in fields {
+                let wei = match object.get(*field) {
+                    Some(Value::Number(n)) => n.as_u64().map(U256::from),
+                    Some(Value::String(s)) => U256::from_dec_str(s).ok(),
+                    _ => None,
+                };
+                if let Some(wei) = wei {
+                    object.insert(format!("{}_eth", field), Value::String(format_ether(wei)));
+                }
+            }
+        }
+        _ => {}
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const U256_MAX: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    fn wei(digits: &str) -> U256 {
+        U256::from_dec_str(digits).unwrap()
+    }
+
+    #[test]
+    fn parses_decimal_forms() {
+        assert_eq!(parse_ether("1.5"), Ok(wei("1500000000000000000")));
+        assert_eq!(parse_ether(".5"), Ok(wei("500000000000000000")));
+        assert_eq!(parse_ether("1."), Ok(wei("1000000000000000000")));
+        assert_eq!(parse_ether("0"), Ok(U256::zero()));
+        assert_eq!(parse_ether("0.000000000000000001"), Ok(U256::one()));
+        assert_eq!(parse_ether(" 2 "), Ok(wei("2000000000000000000")));
+        assert_eq!(parse_units("007.50", 2), Ok(U256::from(750)));
+    }
+
+    #[test]
+    fn rejects_more_decimals_than_allowed() {
+        assert_eq!(
+            parse_units("1.0000001", 6),
+            Err(UnitsError::TooManyDecimals {
+                value: "1.0000001".to_string(),
+                decimals: 6
+            })
+        );
+        assert!(matches!(
+            parse_ether("0.0000000000000000001"),
+            Err(UnitsError::TooManyDecimals { .. })
+        ));
+        assert!(matches!(parse_units("1.5", 0), Err(UnitsError::TooManyDecimals { .. })));
+        // Zeros à direita não perdem precisão.
+        assert_eq!(parse_units("1.5000000", 6), Ok(U256::from(1_500_000)));
+        assert_eq!(parse_units("3.0", 0), Ok(U256::from(3)));
+    }
+
+    #[test]
+    fn rejects_negative_and_garbage() {
+        assert_eq!(parse_ether(""), Err(UnitsError::Empty));
+        assert_eq!(parse_ether("   "), Err(UnitsError::Empty));
+        for value in ["-1", "+1", ".", "abc", "1e18", "1.2.3", "1,5", "0x10", "1 000", "١"] {
+            assert_eq!(
+                parse_ether(value),
+                Err(UnitsError::Invalid(value.to_string())),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_overflow_past_u256() {
+        assert_eq!(parse_units(U256_MAX, 0), Ok(U256::MAX));
+        let over = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert_eq!(parse_units(over, 0), Err(UnitsError::Overflow(over.to_string())));
+        assert!(matches!(parse_ether(U256_MAX), Err(UnitsError::Overflow(_))));
+    }
+
+    #[test]
+    fn formats_without_trailing_zeros() {
+        assert_eq!(format_ether(wei("1500000000000000000")), "1.5");
+        assert_eq!(format_ether(U256::one()), "0.000000000000000001");
+        assert_eq!(format_ether(U256::zero()), "0");
+        assert_eq!(format_ether(wei("2000000000000000000")), "2");
+        assert_eq!(format_gwei(wei("1500000000")), "1.5");
+        assert_eq!(format_units(U256::from(42), 0), "42");
+    }
+
+    #[test]
+    fn u256_max_round_trips() {
+        for decimals in [18, 6] {
+            let formatted = format_units(U256::MAX, decimals);
+            assert_eq!(parse_units(&formatted, decimals), Ok(U256::MAX), "{} casas", decimals);
+        }
+        assert_eq!(
+            format_units(U256::MAX, 18),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(
+            format_units(U256::MAX, 6),
+            "115792089237316195423570985008687907853269984665640564039457584007913129.639935"
+        );
+    }
+
+    #[test]
+    fn wei_serializes_as_decimal_string() {
+        assert_eq!(serde_json::to_value(Wei(U256::MAX)).unwrap(), json!(U256_MAX));
+        assert_eq!(serde_json::to_value(Wei::from(5)).unwrap(), json!("5"));
+    }
+
+    #[test]
+    fn wei_deserializes_strings_and_small_integers() {
+        assert_eq!(serde_json::from_value::<Wei>(json!(U256_MAX)).unwrap(), Wei(U256::MAX));
+        assert_eq!(serde_json::from_value::<Wei>(json!(7)).unwrap(), Wei::from(7));
+        assert_eq!(serde_json::from_value::<Wei>(json!(u64::MAX)).unwrap(), Wei::from(u64::MAX));
+
+        for invalid in [json!(-1), json!(1.5), json!(1e30), json!("1.5"), json!("-3"), json!(null)] {
+            assert!(serde_json::from_value::<Wei>(invalid.clone()).is_err(), "{}", invalid);
+        }
+        let over = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert!(serde_json::from_value::<Wei>(json!(over)).is_err());
+    }
+
+    #[test]
+    fn with_ether_adds_eth_fields() {
+        let value = with_ether(
+            json!({ "price": "1500000000000000000", "value": 2, "other": "3" }),
+            &["price", "value", "missing"],
+        );
+        assert_eq!(value["price"], json!("1500000000000000000"));
+        assert_eq!(value["price_eth"], json!("1.5"));
+        assert_eq!(value["value_eth"], json!("0.000000000000000002"));
+        assert!(value.get("other_eth").is_none());
+        assert!(value.get("missing_eth").is_none());
+
+        let list = with_ether(json!([{ "price": "1" }, { "price": "abc" }]), &["price"]);
+        assert_eq!(list[0]["price_eth"], json!("0.000000000000000001"));
+        assert!(list[1].get("price_eth").is_none());
+        assert_eq!(with_ether(json!("1"), &["price"]), json!("1"));
+    }
+}